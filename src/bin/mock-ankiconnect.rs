@@ -0,0 +1,412 @@
+//! A standalone mock AnkiConnect server
+//!
+//! Serves the AnkiConnect JSON protocol on top of an in-memory [`FakeAnki`] collection,
+//! so tools written in other languages can be tested without a running Anki.
+//!
+//! Besides the AnkiConnect protocol on `POST /`, the server offers two endpoints for
+//! fixtures: `GET /collection` returns the collection as JSON and `PUT /collection`
+//! replaces it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use ankiconnect_rs::fake::{Collection, FakeAnki};
+use ankiconnect_rs::AnkiConnectResponse;
+use serde::Deserialize;
+use serde_json::Value;
+
+const USAGE: &str = "\
+Usage: mock-ankiconnect [OPTIONS]
+
+Options:
+  --host <HOST>     Address to listen on [default: 127.0.0.1]
+  --port <PORT>     Port to listen on, 0 picks a free port [default: 8765]
+  --load <FILE>     Load the collection from a JSON fixture
+  --dump <FILE>     Write the collection to a JSON file after every request
+  --api-key <KEY>   Require this API key, like AnkiConnect's `apiKey` setting
+  --empty           Start without the default deck and stock note types
+  -h, --help        Print this help
+";
+
+/// How long a client may take to send its request before the connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The largest request body accepted, large enough for media uploads
+const MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+/// The error AnkiConnect reports for a missing or wrong API key
+const INVALID_KEY_ERROR: &str = "valid api key must be provided";
+
+static DUMP_LOCK: Mutex<()> = Mutex::new(());
+
+struct Options {
+    host: String,
+    port: u16,
+    load: Option<PathBuf>,
+    dump: Option<PathBuf>,
+    api_key: Option<String>,
+    empty: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            host: "127.0.0.1".to_string(),
+            port: 8765,
+            load: None,
+            dump: None,
+            api_key: None,
+            empty: false,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {}", name))
+            };
+            match arg.as_str() {
+                "--host" => options.host = value("--host")?,
+                "--port" => {
+                    options.port = value("--port")?
+                        .parse()
+                        .map_err(|e| format!("invalid port: {}", e))?
+                }
+                "--load" => options.load = Some(value("--load")?.into()),
+                "--dump" => options.dump = Some(value("--dump")?.into()),
+                "--api-key" => options.api_key = Some(value("--api-key")?),
+                "--empty" => options.empty = true,
+                "-h" | "--help" => {
+                    print!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                _ => return Err(format!("unknown argument: {}", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// A request as sent by AnkiConnect clients
+#[derive(Deserialize)]
+struct AnkiConnectRequest {
+    action: String,
+    #[serde(default)]
+    key: Option<String>,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+impl AnkiConnectRequest {
+    /// Checks the API key of the request
+    ///
+    /// The keys of the actions inside `multi` are checked by [`handle_multi`].
+    fn is_authorized(&self, api_key: Option<&str>) -> bool {
+        has_valid_key(&self.action, self.key.as_deref(), api_key)
+    }
+}
+
+/// Checks an API key like AnkiConnect, which always lets clients request permission
+fn has_valid_key(action: &str, key: Option<&str>, api_key: Option<&str>) -> bool {
+    api_key.is_none() || action == "requestPermission" || key == api_key
+}
+
+/// Runs the actions of a `multi` request one by one
+///
+/// Like in AnkiConnect, an action with a missing or wrong API key fails on its own
+/// instead of failing the whole request.
+fn handle_multi(
+    fake: &FakeAnki,
+    params: Option<Value>,
+    api_key: Option<&str>,
+) -> AnkiConnectResponse {
+    let actions = match params.as_ref().and_then(|params| params.get("actions")) {
+        Some(Value::Array(actions)) if api_key.is_some() => actions,
+        _ => return fake.handle("multi", params),
+    };
+
+    let responses: Vec<AnkiConnectResponse> = actions
+        .iter()
+        .map(|inner| {
+            let action = inner
+                .get("action")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let key = inner.get("key").and_then(Value::as_str);
+            if !has_valid_key(action, key, api_key) {
+                return AnkiConnectResponse::error(INVALID_KEY_ERROR);
+            }
+            let params = inner.get("params").filter(|params| !params.is_null());
+            fake.handle(action, params.cloned())
+        })
+        .collect();
+
+    match serde_json::to_value(responses) {
+        Ok(result) => AnkiConnectResponse::success(result),
+        Err(e) => AnkiConnectResponse::error(e.to_string()),
+    }
+}
+
+/// A parsed HTTP request
+struct HttpRequest {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// An HTTP response to send back
+struct HttpResponse {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn json(body: &impl serde::Serialize) -> Self {
+        Self {
+            status: "200 OK",
+            content_type: "application/json",
+            body: serde_json::to_vec(body).unwrap_or_default(),
+        }
+    }
+
+    fn text(status: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: body.into().into_bytes(),
+        }
+    }
+}
+
+fn main() {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let collection = match &options.load {
+        Some(path) => match load_fixture(path) {
+            Ok(collection) => collection,
+            Err(e) => {
+                eprintln!("error: could not load {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        None if options.empty => Collection::empty(),
+        None => Collection::new(),
+    };
+    let fake = Arc::new(FakeAnki::with_collection(collection));
+    let options = Arc::new(options);
+
+    let listener = match TcpListener::bind((options.host.as_str(), options.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!(
+                "error: could not listen on {}:{}: {}",
+                options.host, options.port, e
+            );
+            std::process::exit(1);
+        }
+    };
+    // Tests starting the server with `--port 0` read the port from this line
+    match listener.local_addr() {
+        Ok(addr) => println!("Listening on http://{}", addr),
+        Err(e) => eprintln!("warning: could not determine local address: {}", e),
+    }
+
+    // Each connection gets its own thread, so a client that connects without sending
+    // anything doesn't block the others. Like AnkiConnect itself, the fake still runs
+    // one action at a time.
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("warning: failed to accept connection: {}", e);
+                continue;
+            }
+        };
+
+        let fake = Arc::clone(&fake);
+        let options = Arc::clone(&options);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &fake, &options) {
+                eprintln!("warning: {}", e);
+            }
+        });
+    }
+}
+
+fn load_fixture(path: &Path) -> Result<Collection, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn handle_connection(stream: TcpStream, fake: &FakeAnki, options: &Options) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let Some(request) = read_request(&mut reader)? else {
+        return Ok(());
+    };
+
+    let response = route(&request, fake, options.api_key.as_deref());
+    if request.method != "GET" && request.method != "OPTIONS" {
+        if let Some(path) = &options.dump {
+            // Connections are handled concurrently, keep them from writing at once
+            let _dumping = DUMP_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            let content = serde_json::to_string_pretty(&fake.collection())?;
+            std::fs::write(path, content)?;
+        }
+    }
+
+    write_response(stream, &response)
+}
+
+fn route(request: &HttpRequest, fake: &FakeAnki, api_key: Option<&str>) -> HttpResponse {
+    match (request.method.as_str(), request.path.as_str()) {
+        // CORS preflight requests of browser extensions
+        ("OPTIONS", _) => HttpResponse::text("200 OK", ""),
+        ("GET", "/collection") => HttpResponse::json(&fake.collection()),
+        ("PUT", "/collection") => match serde_json::from_slice::<Collection>(&request.body) {
+            Ok(collection) => {
+                fake.replace_collection(collection);
+                HttpResponse::json(&Value::Null)
+            }
+            Err(e) => HttpResponse::text("400 Bad Request", format!("invalid collection: {}", e)),
+        },
+        // AnkiConnect answers plain GET requests with its name and version
+        ("GET", _) => HttpResponse::text("200 OK", "AnkiConnect v.6"),
+        ("POST", _) => match serde_json::from_slice::<AnkiConnectRequest>(&request.body) {
+            Ok(call) if !call.is_authorized(api_key) => {
+                HttpResponse::json(&AnkiConnectResponse::error(INVALID_KEY_ERROR))
+            }
+            Ok(call) if call.action == "multi" => {
+                HttpResponse::json(&handle_multi(fake, call.params, api_key))
+            }
+            Ok(call) => {
+                let mut response = fake.handle(&call.action, call.params);
+                if call.action == "requestPermission" {
+                    if let Some(Value::Object(result)) = &mut response.result {
+                        result.insert("requireApikey".to_string(), Value::Bool(api_key.is_some()));
+                    }
+                }
+                HttpResponse::json(&response)
+            }
+            Err(e) => HttpResponse::json(&AnkiConnectResponse::error(e.to_string())),
+        },
+        _ => HttpResponse::text("405 Method Not Allowed", "method not allowed"),
+    }
+}
+
+/// Reads a single HTTP/1.1 request, returning `None` if the client closed the connection
+fn read_request(reader: &mut impl BufRead) -> std::io::Result<Option<HttpRequest>> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut content_length = 0;
+    let mut chunked = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().map_err(invalid_data)?;
+                check_body_size(content_length)?;
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            }
+        }
+    }
+
+    let body = if chunked {
+        read_chunked_body(reader)?
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        body
+    };
+
+    Ok(Some(HttpRequest { method, path, body }))
+}
+
+fn read_chunked_body(reader: &mut impl BufRead) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line)?;
+        let size = size_line.trim().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size, 16).map_err(invalid_data)?;
+        check_body_size(body.len().saturating_add(size))?;
+
+        if size == 0 {
+            // Skip the trailer headers up to the empty line that ends the body
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line)? == 0
+                    || line.trim_end_matches(['\r', '\n']).is_empty()
+                {
+                    return Ok(body);
+                }
+            }
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        let mut line_end = [0; 2];
+        reader.read_exact(&mut line_end)?;
+        if &line_end != b"\r\n" {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "chunk is not followed by CRLF",
+            ));
+        }
+    }
+}
+
+fn check_body_size(size: usize) -> std::io::Result<()> {
+    if size > MAX_BODY_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("request body of {} bytes is too large", size),
+        ));
+    }
+    Ok(())
+}
+
+fn invalid_data(e: impl std::error::Error + Send + Sync + 'static) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+fn write_response(mut stream: TcpStream, response: &HttpResponse) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\n\
+         Content-Type: {}\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Headers: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, PUT, OPTIONS\r\n\
+         Connection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
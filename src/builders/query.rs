@@ -194,6 +194,7 @@ impl QueryBuilder {
     }
 
     /// Negates the next condition
+    #[allow(clippy::should_implement_trait)]
     pub fn not(mut self) -> Self {
         self.negated = true;
         self
@@ -274,9 +275,7 @@ impl QueryBuilder {
         let needs_escape = |c: char| matches!(c, '"' | '*' | '_' | '\\' | '(' | ')' | ':' | '-');
 
         let mut result = String::with_capacity(s.len());
-        let mut chars = s.chars();
-
-        for c in chars {
+        for c in s.chars() {
             if needs_escape(c) {
                 result.push('\\');
             }
@@ -0,0 +1,174 @@
+//! Caching of read-mostly metadata like deck and model names
+//!
+//! [`CachingSender`] wraps another [`RequestSender`] and keeps the responses of
+//! actions that read decks, models and their fields for a configurable time. Any
+//! action that may change this metadata clears the cache, as does
+//! [`CachingSender::invalidate`].
+//!
+//! The cache only sees requests sent through it, so changes made in Anki itself or by
+//! other clients show up once the entries expire. Use
+//! [`AnkiClient::cached`](crate::AnkiClient::cached) to enable it for a client.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::http::{AnkiConnectResponse, RequestSender};
+
+/// Actions whose responses are cached
+const CACHED_ACTIONS: &[&str] = &[
+    "deckNames",
+    "deckNamesAndIds",
+    "findModelsById",
+    "findModelsByName",
+    "modelFieldNames",
+    "modelNames",
+    "modelNamesAndIds",
+    "modelStyling",
+    "modelTemplates",
+];
+
+/// Actions that may change decks, models or fields and thus clear the cache
+const INVALIDATING_ACTIONS: &[&str] = &[
+    "changeDeck",
+    "createDeck",
+    "createModel",
+    "deleteDecks",
+    "guiImportFile",
+    "importPackage",
+    "loadProfile",
+    "modelFieldAdd",
+    "modelFieldRemove",
+    "modelFieldRename",
+    "modelFieldReposition",
+    "modelFieldSetDescription",
+    "modelFieldSetFont",
+    "modelFieldSetFontSize",
+    "modelTemplateAdd",
+    "modelTemplateRemove",
+    "modelTemplateRename",
+    "modelTemplateReposition",
+    "sync",
+    "updateModelStyling",
+    "updateModelTemplates",
+];
+
+/// A cached response and when it was fetched
+struct Entry {
+    fetched: Instant,
+    response: AnkiConnectResponse,
+}
+
+/// The cached responses and how often the cache was cleared
+#[derive(Default)]
+struct Entries {
+    generation: u64,
+    responses: HashMap<String, Entry>,
+}
+
+/// Decorator that caches responses of metadata actions for a fixed time
+pub struct CachingSender {
+    inner: Arc<dyn RequestSender>,
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl CachingSender {
+    /// Creates a cache in front of the given sender
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The sender that actually sends the requests
+    /// * `ttl` - How long a cached response is used
+    pub fn new(inner: Arc<dyn RequestSender>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Gets the sender that actually sends the requests
+    pub(crate) fn inner(&self) -> &Arc<dyn RequestSender> {
+        &self.inner
+    }
+
+    /// Drops all cached responses
+    pub fn invalidate(&self) {
+        let mut entries = self.lock();
+        entries.generation += 1;
+        entries.responses.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RequestSender for CachingSender {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        if !CACHED_ACTIONS.contains(&action) {
+            if !invalidates(action, params.as_ref()) {
+                return self.inner.send_raw(action, params);
+            }
+            // Clear the cache again afterwards, in case a read finished in between
+            self.invalidate();
+            let response = self.inner.send_raw(action, params);
+            self.invalidate();
+            return response;
+        }
+
+        let key = cache_key(action, params.as_ref());
+        let generation = {
+            let entries = self.lock();
+            if let Some(entry) = entries.responses.get(&key) {
+                if entry.fetched.elapsed() < self.ttl {
+                    return Ok(entry.response.clone());
+                }
+            }
+            entries.generation
+        };
+
+        let response = self.inner.send_raw(action, params)?;
+        // Errors like a missing model are not cached, the model may be created later.
+        // Neither are responses that may predate a change made while they were fetched.
+        let mut entries = self.lock();
+        if response.error.is_none() && entries.generation == generation {
+            entries.responses.insert(
+                key,
+                Entry {
+                    fetched: Instant::now(),
+                    response: response.clone(),
+                },
+            );
+        }
+        Ok(response)
+    }
+}
+
+/// Checks if an action, or any action of a `multi` request, may change the metadata
+fn invalidates(action: &str, params: Option<&Value>) -> bool {
+    if action != "multi" {
+        return INVALIDATING_ACTIONS.contains(&action);
+    }
+
+    let actions = params
+        .and_then(|params| params.get("actions"))
+        .and_then(Value::as_array);
+    actions.into_iter().flatten().any(|inner| {
+        inner
+            .get("action")
+            .and_then(Value::as_str)
+            .is_some_and(|action| invalidates(action, inner.get("params")))
+    })
+}
+
+fn cache_key(action: &str, params: Option<&Value>) -> String {
+    match params {
+        Some(params) => format!("{} {}", action, params),
+        None => action.to_string(),
+    }
+}
@@ -0,0 +1,194 @@
+//! Recording and replaying of AnkiConnect sessions
+//!
+//! [`RecordingSender`] wraps another [`RequestSender`] and writes every request and
+//! its response to a cassette file, one JSON object per line. [`ReplaySender`] serves
+//! such a cassette back in order, so a session recorded against a real collection can
+//! be turned into a regression test.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use ankiconnect_rs::cassette::{RecordingSender, ReplaySender};
+//! use ankiconnect_rs::http::HttpRequestSender;
+//! use ankiconnect_rs::AnkiClient;
+//!
+//! # fn main() -> ankiconnect_rs::Result<()> {
+//! // Record a session against the running Anki
+//! let http = Arc::new(HttpRequestSender::new("localhost", 8765));
+//! let client = AnkiClient::with_sender(Arc::new(RecordingSender::create("session.jsonl", http)?));
+//! let decks = client.decks().get_all()?;
+//!
+//! // Later, replay it without Anki
+//! let replay = Arc::new(ReplaySender::from_file("session.jsonl")?);
+//! let client = AnkiClient::with_sender(replay.clone());
+//! assert_eq!(client.decks().get_all()?.len(), decks.len());
+//! assert_eq!(replay.remaining(), 0);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AnkiError, Result};
+use crate::http::{AnkiConnectResponse, RequestSender};
+
+/// A single request and the response AnkiConnect sent for it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    /// The action that was requested
+    pub action: String,
+    /// The parameters of the action
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// The response envelope AnkiConnect sent
+    pub response: AnkiConnectResponse,
+}
+
+/// Decorator that records all requests and responses to a cassette file
+///
+/// Only requests that got a response are recorded; failures of the transport are
+/// passed through. The API key is added by the inner sender and never recorded.
+pub struct RecordingSender {
+    inner: Arc<dyn RequestSender>,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl RecordingSender {
+    /// Creates a recorder that writes to a new cassette file, replacing an existing one
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cassette file
+    /// * `inner` - The sender that actually sends the requests
+    pub fn create<P: AsRef<Path>>(path: P, inner: Arc<dyn RequestSender>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            AnkiError::CassetteError(format!("Could not create {}: {}", path.display(), e))
+        })?;
+
+        Ok(Self {
+            inner,
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn record(&self, interaction: &Interaction) -> Result<()> {
+        let line =
+            serde_json::to_string(interaction).map_err(|e| AnkiError::JsonError(e.to_string()))?;
+
+        // Flush after every line, so the cassette is complete even if the process dies
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        writeln!(writer, "{}", line)
+            .and_then(|_| writer.flush())
+            .map_err(|e| AnkiError::CassetteError(format!("Could not write cassette: {}", e)))
+    }
+}
+
+impl RequestSender for RecordingSender {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        let response = self.inner.send_raw(action, params.clone())?;
+        self.record(&Interaction {
+            action: action.to_string(),
+            params,
+            response: response.clone(),
+        })?;
+        Ok(response)
+    }
+}
+
+/// Sender that serves the responses of a cassette in the order they were recorded
+///
+/// Every request must match the next recorded one by action and parameters, otherwise
+/// [`AnkiError::CassetteError`] is returned.
+pub struct ReplaySender {
+    interactions: Mutex<VecDeque<Interaction>>,
+}
+
+impl ReplaySender {
+    /// Creates a replay sender for the given interactions
+    pub fn new(interactions: Vec<Interaction>) -> Self {
+        Self {
+            interactions: Mutex::new(interactions.into()),
+        }
+    }
+
+    /// Loads a cassette file written by [`RecordingSender`]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cassette file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            AnkiError::CassetteError(format!("Could not open {}: {}", path.display(), e))
+        })?;
+
+        let mut interactions = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| {
+                AnkiError::CassetteError(format!("Could not read {}: {}", path.display(), e))
+            })?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let interaction = serde_json::from_str(&line).map_err(|e| {
+                AnkiError::CassetteError(format!(
+                    "Invalid interaction on line {} of {}: {}",
+                    index + 1,
+                    path.display(),
+                    e
+                ))
+            })?;
+            interactions.push(interaction);
+        }
+
+        Ok(Self::new(interactions))
+    }
+
+    /// Gets the number of recorded interactions that were not replayed yet
+    pub fn remaining(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Interaction>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RequestSender for ReplaySender {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        let mut interactions = self.lock();
+        match interactions.pop_front() {
+            Some(expected) if expected.action == action && expected.params == params => {
+                Ok(expected.response)
+            }
+            Some(expected) => {
+                let error = AnkiError::CassetteError(format!(
+                    "Unexpected request '{}' with params {}: expected '{}' with params {}",
+                    action,
+                    params.unwrap_or_default(),
+                    expected.action,
+                    expected.params.clone().unwrap_or_default()
+                ));
+                // Keep the expected interaction, so the mismatch doesn't cascade
+                interactions.push_front(expected);
+                Err(error)
+            }
+            None => Err(AnkiError::CassetteError(format!(
+                "Unexpected request '{}': the cassette is exhausted",
+                action
+            ))),
+        }
+    }
+}
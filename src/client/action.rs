@@ -0,0 +1,112 @@
+//! Typed AnkiConnect actions
+//!
+//! The [`Action`] trait describes an AnkiConnect action by its name, parameters and
+//! result, so any action can be called through
+//! [`AnkiClient::execute`](crate::AnkiClient::execute), including ones this crate
+//! does not wrap yet.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::client::request::{ApiReflectDto, ApiReflectParams, PermissionDto};
+use crate::error::{AnkiError, Result};
+
+/// An AnkiConnect action with typed parameters and response
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ankiconnect_rs::{Action, AnkiClient};
+/// use serde::Serialize;
+///
+/// /// Moves the given cards to the end of the new card queue
+/// struct Reposition;
+///
+/// #[derive(Serialize)]
+/// struct RepositionParams {
+///     cards: Vec<u64>,
+/// }
+///
+/// impl Action for Reposition {
+///     const NAME: &'static str = "repositionNewCards";
+///     type Params = RepositionParams;
+///     type Response = serde_json::Value;
+/// }
+///
+/// # fn main() -> ankiconnect_rs::Result<()> {
+/// let client = AnkiClient::new();
+/// client.execute::<Reposition>(RepositionParams { cards: vec![1498938915662] })?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Action {
+    /// The name of the action, as AnkiConnect expects it
+    const NAME: &'static str;
+
+    /// The parameters of the action, `()` if it takes none
+    type Params: Serialize;
+
+    /// The result of the action
+    type Response: DeserializeOwned;
+}
+
+/// Gets the version of the AnkiConnect plugin
+pub struct Version;
+
+impl Action for Version {
+    const NAME: &'static str = "version";
+    type Params = ();
+    type Response = u16;
+}
+
+/// Requests permission to use AnkiConnect
+pub struct RequestPermission;
+
+impl Action for RequestPermission {
+    const NAME: &'static str = "requestPermission";
+    type Params = ();
+    type Response = PermissionDto;
+}
+
+/// Gets the name of the active profile
+pub struct GetActiveProfile;
+
+impl Action for GetActiveProfile {
+    const NAME: &'static str = "getActiveProfile";
+    type Params = ();
+    type Response = String;
+}
+
+/// Lists the actions AnkiConnect supports
+///
+/// Older releases of AnkiConnect lack this action and fail with "unsupported action".
+pub struct ApiReflect;
+
+impl Action for ApiReflect {
+    const NAME: &'static str = "apiReflect";
+    type Params = ApiReflectParams;
+    type Response = ApiReflectDto;
+}
+
+/// Serializes the parameters of an action, treating `null` (e.g. from `()`) as no
+/// parameters
+pub(crate) fn params_to_value<P: Serialize>(params: P) -> Result<Option<Value>> {
+    match serde_json::to_value(params) {
+        Ok(Value::Null) => Ok(None),
+        Ok(params) => Ok(Some(params)),
+        Err(e) => Err(AnkiError::JsonError(e.to_string())),
+    }
+}
+
+/// Checks that the parameters of a raw action are a JSON object or `null`
+pub(crate) fn raw_params(action: &str, params: Value) -> Result<Option<Value>> {
+    match params {
+        Value::Null => Ok(None),
+        Value::Object(_) => Ok(Some(params)),
+        _ => Err(AnkiError::ValidationError(format!(
+            "Parameters of '{}' must be a JSON object",
+            action
+        ))),
+    }
+}
@@ -1,13 +1,40 @@
-use crate::client::{CardClient, DeckClient, MediaClient, ModelClient};
-use crate::http::HttpRequestSender;
+use crate::cache::CachingSender;
+use crate::client::action::{self, Action, GetActiveProfile, RequestPermission, Version};
+use crate::client::capabilities::{self, CapabilityCheck, SharedCapabilities};
+use crate::client::{AnkiClientBuilder, Batch, CardClient, DeckClient, MediaClient, ModelClient};
+use crate::http::{self, HttpRequestSender, RequestSender};
+use crate::models::{Capabilities, Health, Permission};
+use crate::redact;
 use crate::AnkiError;
+use serde_json::Value;
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait between two attempts to reach AnkiConnect
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 /// The main client for interacting with Anki via AnkiConnect
 ///
 /// This is the primary entry point for the library. It provides access to specialized
 /// clients for different aspects of Anki functionality.
+///
+/// The client is a cheap handle: clones share the sender, cache and capabilities, so
+/// it can be stored in application state or handed to worker threads without an extra
+/// `Arc`. It is `Send + Sync`, as every [`RequestSender`] is.
+///
+/// The wrapper methods of the sub-clients are checked against the
+/// [capabilities](AnkiClient::capabilities) of the connected AnkiConnect and fail with
+/// [`AnkiError::ActionNotSupported`] instead of sending an unsupported action.
+/// [`AnkiClient::execute`], [`AnkiClient::raw`] and [`AnkiClient::batch`] send their
+/// actions unchecked, so they also reach actions `apiReflect` doesn't list.
+#[derive(Clone)]
 pub struct AnkiClient {
+    /// The sender without the capability check, used for `execute`, `raw` and `batch`
+    /// and wrapped by [`AnkiClient::cached`]
+    transport: Arc<dyn RequestSender>,
+    cache: Option<Arc<CachingSender>>,
+    capabilities: SharedCapabilities,
     cards_client: CardClient,
     decks_client: DeckClient,
     media_client: MediaClient,
@@ -20,20 +47,290 @@ impl AnkiClient {
         Self::with_connection("localhost", 8765)
     }
 
+    /// Creates a builder for a client with custom settings
+    ///
+    /// Use this to connect to a full URL (e.g. behind an HTTPS reverse proxy), or to
+    /// configure an API key, proxy, timeouts or user agent.
+    pub fn builder() -> AnkiClientBuilder {
+        AnkiClientBuilder::new()
+    }
+
+    /// Creates a new client configured from the environment
+    ///
+    /// See [`AnkiClientBuilder::from_env`] for the variables that are read.
+    pub fn from_env() -> Result<Self, AnkiError> {
+        AnkiClientBuilder::from_env()?.build()
+    }
+
     /// Creates a new client with a custom host and port
     pub fn with_connection(host: &str, port: u16) -> Self {
-        let sender = Arc::new(HttpRequestSender::new(host, port));
+        Self::with_sender(Arc::new(HttpRequestSender::new(host, port)))
+    }
+
+    /// Creates a new client with a custom host and port that authenticates with the
+    /// given API key
+    ///
+    /// Use this if AnkiConnect is configured with an `apiKey`. The key is sent with
+    /// every request, including each action of a batch.
+    pub fn with_api_key(host: &str, port: u16, api_key: &str) -> Self {
+        Self::with_sender(Arc::new(
+            HttpRequestSender::new(host, port).with_api_key(api_key),
+        ))
+    }
+
+    /// Creates a new client with a custom host and port after performing the permission
+    /// handshake
+    ///
+    /// Fails fast if AnkiConnect denies permission, or if it requires an API key and none
+    /// was given.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host AnkiConnect is running on
+    /// * `port` - The port AnkiConnect is listening on
+    /// * `api_key` - The API key to send with every request, if any
+    ///
+    /// # Returns
+    ///
+    /// The connected client, [`AnkiError::PermissionDenied`] if permission was denied, or
+    /// [`AnkiError::ActionFailed`] with
+    /// [`AnkiConnectError::InvalidApiKey`](crate::AnkiConnectError::InvalidApiKey) if an
+    /// API key is required but missing
+    pub fn connect(host: &str, port: u16, api_key: Option<&str>) -> Result<Self, AnkiError> {
+        let mut sender = HttpRequestSender::new(host, port);
+        if let Some(api_key) = api_key {
+            sender = sender.with_api_key(api_key);
+        }
+        let client = Self::with_sender(Arc::new(sender));
+
+        match client.request_permission()? {
+            Permission::Granted {
+                require_api_key: true,
+                ..
+            } if api_key.is_none() => Err(http::action_failed(
+                "requestPermission",
+                None,
+                "valid api key must be provided".to_string(),
+            )),
+            Permission::Granted { .. } => Ok(client),
+            Permission::Denied => Err(AnkiError::PermissionDenied),
+        }
+    }
+
+    /// Creates a new client that sends all requests through the given sender
+    ///
+    /// Use this to plug in a custom transport, a decorator around
+    /// [`HttpRequestSender`] or a test double.
+    pub fn with_sender(sender: Arc<dyn RequestSender>) -> Self {
+        Self::assemble(sender, None, SharedCapabilities::default())
+    }
+
+    fn assemble(
+        transport: Arc<dyn RequestSender>,
+        cache: Option<Arc<CachingSender>>,
+        capabilities: SharedCapabilities,
+    ) -> Self {
+        let sender: Arc<dyn RequestSender> = Arc::new(CapabilityCheck::new(
+            Arc::clone(&transport),
+            Arc::clone(&capabilities),
+        ));
         Self {
             cards_client: CardClient::new(Arc::clone(&sender)),
             decks_client: DeckClient::new(Arc::clone(&sender)),
             media_client: MediaClient::new(Arc::clone(&sender)),
             models_client: ModelClient::new(sender),
+            transport,
+            cache,
+            capabilities,
+        }
+    }
+
+    /// Enables caching of deck, model and field names for this client
+    ///
+    /// Responses of these read-mostly actions are reused for `ttl`. The cache is
+    /// cleared whenever this client changes decks or models, or on
+    /// [`AnkiClient::invalidate_cache`]. Changes made in Anki itself are only seen once
+    /// the entries expire. Calling this on a client that already caches replaces its
+    /// cache with an empty one using the new `ttl`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long a cached response is used
+    pub fn cached(self, ttl: Duration) -> Self {
+        let transport = match self.cache {
+            Some(cache) => Arc::clone(cache.inner()),
+            None => self.transport,
+        };
+        let cache = Arc::new(CachingSender::new(transport, ttl));
+        Self::assemble(cache.clone(), Some(cache), self.capabilities)
+    }
+
+    /// Drops all cached responses, if caching is enabled
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate();
         }
     }
 
     /// Gets the version of the AnkiConnect plugin
     pub fn version(&self) -> Result<u16, AnkiError> {
-        self.cards_client.get_version()
+        self.execute::<Version>(())
+    }
+
+    /// Gets what the connected AnkiConnect supports
+    ///
+    /// The version and the supported actions are queried with `version` and
+    /// `apiReflect` once, by this method or by the first request after AnkiConnect
+    /// answered one, and reused afterwards. Wrapper methods fail with [`AnkiError::ActionNotSupported`] instead
+    /// of sending an action the instance lacks.
+    ///
+    /// # Returns
+    ///
+    /// The capabilities of the connected AnkiConnect
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ankiconnect_rs::AnkiClient;
+    ///
+    /// # fn main() -> ankiconnect_rs::Result<()> {
+    /// let client = AnkiClient::new();
+    /// if client.capabilities()?.supports("setDueDate") {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> Result<Capabilities, AnkiError> {
+        capabilities::load(&self.capabilities, self.transport.as_ref())
+    }
+
+    /// Waits until AnkiConnect answers requests, e.g. after starting Anki
+    ///
+    /// Polls [`AnkiClient::version`] until it succeeds. Errors reported by AnkiConnect
+    /// itself (like a missing API key) are returned right away, as waiting won't fix
+    /// them. Configure a read timeout to keep a hanging request from exceeding `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait at most
+    ///
+    /// # Returns
+    ///
+    /// The version of the AnkiConnect plugin, or [`AnkiError::NotReady`] if it could not
+    /// be reached in time
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<u16, AnkiError> {
+        let started = Instant::now();
+        loop {
+            let error = match self.version() {
+                Ok(version) => return Ok(version),
+                Err(error @ (AnkiError::HttpError(_) | AnkiError::RequestFailed { .. })) => error,
+                Err(error) => return Err(error),
+            };
+
+            let waited = started.elapsed();
+            if waited >= timeout {
+                return Err(AnkiError::NotReady {
+                    waited,
+                    last_error: error.to_string(),
+                });
+            }
+            thread::sleep(READY_POLL_INTERVAL.min(timeout - waited));
+        }
+    }
+
+    /// Checks the health of AnkiConnect
+    ///
+    /// # Returns
+    ///
+    /// Whether AnkiConnect is reachable, how long the `version` request took, the
+    /// version and the active profile. Failures are reported in the result.
+    pub fn health(&self) -> Health {
+        let started = Instant::now();
+        match self.version() {
+            Ok(version) => {
+                let latency = started.elapsed();
+                let profile = self.execute::<GetActiveProfile>(()).ok();
+
+                Health {
+                    reachable: true,
+                    latency: Some(latency),
+                    version: Some(version),
+                    profile,
+                    error: None,
+                }
+            }
+            Err(error) => Health {
+                // AnkiConnect answered, but with an error
+                reachable: error.anki_connect_error().is_some(),
+                latency: None,
+                version: None,
+                profile: None,
+                error: Some(error.to_string()),
+            },
+        }
+    }
+
+    /// Requests permission to use AnkiConnect
+    ///
+    /// This is the only action AnkiConnect accepts without an API key, and the way for
+    /// clients on other origins to ask the user for access.
+    ///
+    /// # Returns
+    ///
+    /// Whether permission was granted and, if so, whether an API key is required
+    pub fn request_permission(&self) -> Result<Permission, AnkiError> {
+        Ok(self.execute::<RequestPermission>(())?.into())
+    }
+
+    /// Executes a typed action
+    ///
+    /// Use this for actions this crate does not wrap, by implementing [`Action`] for
+    /// them. The action is sent even if `apiReflect` doesn't list it.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters for the action
+    ///
+    /// # Returns
+    ///
+    /// The decoded result of the action
+    pub fn execute<A: Action>(&self, params: A::Params) -> Result<A::Response, AnkiError> {
+        let params = action::params_to_value(params)?;
+        let summary = params.as_ref().map(redact::summarize_params);
+        self.transport
+            .send_raw(A::NAME, params)?
+            .decode(A::NAME, summary)
+    }
+
+    /// Executes an action by name with untyped parameters
+    ///
+    /// This is an escape hatch for new or plugin-added actions, so it is sent even if
+    /// `apiReflect` doesn't list the action.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The name of the action
+    /// * `params` - The parameters for the action, a JSON object or `null` for none
+    ///
+    /// # Returns
+    ///
+    /// The raw result of the action, [`Value::Null`] if it returned nothing
+    pub fn raw(&self, action: &str, params: Value) -> Result<Value, AnkiError> {
+        let params = action::raw_params(action, params)?;
+        let summary = params.as_ref().map(redact::summarize_params);
+        self.transport
+            .send_raw(action, params)?
+            .into_result(action, summary)
+    }
+
+    /// Starts a batch of actions that is sent as a single `multi` request
+    ///
+    /// Each action in the batch gets its own result, so a failing action
+    /// does not affect the others. Like [`AnkiClient::raw`], the actions are sent even
+    /// if `apiReflect` doesn't list them.
+    pub fn batch(&self) -> Batch {
+        Batch::new(Arc::clone(&self.transport))
     }
 
     /// Access operations related to cards and notes
@@ -41,20 +338,40 @@ impl AnkiClient {
         &self.cards_client
     }
 
+    /// Gets an owned client for cards and notes, e.g. to move into a worker thread
+    pub fn cards_owned(&self) -> CardClient {
+        self.cards_client.clone()
+    }
+
     /// Access operations related to decks
     pub fn decks(&self) -> &DeckClient {
         &self.decks_client
     }
 
+    /// Gets an owned client for decks, e.g. to move into a worker thread
+    pub fn decks_owned(&self) -> DeckClient {
+        self.decks_client.clone()
+    }
+
     /// Access operations related to media files
     pub fn media(&self) -> &MediaClient {
         &self.media_client
     }
 
+    /// Gets an owned client for media files, e.g. to move into a worker thread
+    pub fn media_owned(&self) -> MediaClient {
+        self.media_client.clone()
+    }
+
     /// Access operations related to note types (models)
     pub fn models(&self) -> &ModelClient {
         &self.models_client
     }
+
+    /// Gets an owned client for note types (models), e.g. to move into a worker thread
+    pub fn models_owned(&self) -> ModelClient {
+        self.models_client.clone()
+    }
 }
 
 impl Default for AnkiClient {
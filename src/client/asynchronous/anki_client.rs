@@ -0,0 +1,236 @@
+use crate::client::action::{self, Action, RequestPermission, Version};
+use crate::client::asynchronous::{
+    AsyncCardClient, AsyncDeckClient, AsyncMediaClient, AsyncModelClient,
+};
+use crate::client::capabilities::{self, AsyncCapabilityCheck, SharedCapabilities};
+use crate::http::{self, AsyncHttpRequestSender, AsyncRequestSender};
+use crate::models::{Capabilities, Permission};
+use crate::redact;
+use crate::AnkiError;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// The async client for interacting with Anki via AnkiConnect
+///
+/// This is the async counterpart of [`AnkiClient`](crate::AnkiClient). It provides
+/// access to specialized clients for different aspects of Anki functionality.
+///
+/// Like the blocking client, it is a cheap `Clone + Send + Sync` handle, so it can be
+/// moved into spawned tasks.
+///
+/// The wrapper methods of the sub-clients are checked against the
+/// [capabilities](AsyncAnkiClient::capabilities) of the connected AnkiConnect, while
+/// [`AsyncAnkiClient::execute`] and [`AsyncAnkiClient::raw`] send their actions
+/// unchecked.
+#[derive(Clone)]
+pub struct AsyncAnkiClient {
+    /// The sender without the capability check, used for `execute` and `raw`
+    transport: Arc<dyn AsyncRequestSender>,
+    capabilities: SharedCapabilities,
+    cards_client: AsyncCardClient,
+    decks_client: AsyncDeckClient,
+    media_client: AsyncMediaClient,
+    models_client: AsyncModelClient,
+}
+
+impl AsyncAnkiClient {
+    /// Creates a new client with the default connection (localhost:8765)
+    pub fn new() -> Self {
+        Self::with_connection("localhost", 8765)
+    }
+
+    /// Creates a new client with a custom host and port
+    pub fn with_connection(host: &str, port: u16) -> Self {
+        Self::with_sender(Arc::new(AsyncHttpRequestSender::new(host, port)))
+    }
+
+    /// Creates a new client with a custom host and port that authenticates with the
+    /// given API key
+    ///
+    /// Use this if AnkiConnect is configured with an `apiKey`. The key is sent with
+    /// every request.
+    pub fn with_api_key(host: &str, port: u16, api_key: &str) -> Self {
+        Self::with_sender(Arc::new(
+            AsyncHttpRequestSender::new(host, port).with_api_key(api_key),
+        ))
+    }
+
+    /// Creates a new client with a custom host and port after performing the permission
+    /// handshake
+    ///
+    /// Fails fast if AnkiConnect denies permission, or if it requires an API key and none
+    /// was given.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host AnkiConnect is running on
+    /// * `port` - The port AnkiConnect is listening on
+    /// * `api_key` - The API key to send with every request, if any
+    ///
+    /// # Returns
+    ///
+    /// The connected client, [`AnkiError::PermissionDenied`] if permission was denied, or
+    /// [`AnkiError::ActionFailed`] with
+    /// [`AnkiConnectError::InvalidApiKey`](crate::AnkiConnectError::InvalidApiKey) if an
+    /// API key is required but missing
+    pub async fn connect(host: &str, port: u16, api_key: Option<&str>) -> Result<Self, AnkiError> {
+        let mut sender = AsyncHttpRequestSender::new(host, port);
+        if let Some(api_key) = api_key {
+            sender = sender.with_api_key(api_key);
+        }
+        let client = Self::with_sender(Arc::new(sender));
+
+        match client.request_permission().await? {
+            Permission::Granted {
+                require_api_key: true,
+                ..
+            } if api_key.is_none() => Err(http::action_failed(
+                "requestPermission",
+                None,
+                "valid api key must be provided".to_string(),
+            )),
+            Permission::Granted { .. } => Ok(client),
+            Permission::Denied => Err(AnkiError::PermissionDenied),
+        }
+    }
+
+    /// Creates a new client that sends all requests through the given sender
+    ///
+    /// Use this to plug in a custom transport, a decorator around
+    /// [`AsyncHttpRequestSender`] or a test double.
+    pub fn with_sender(transport: Arc<dyn AsyncRequestSender>) -> Self {
+        let capabilities = SharedCapabilities::default();
+        let sender: Arc<dyn AsyncRequestSender> = Arc::new(AsyncCapabilityCheck::new(
+            Arc::clone(&transport),
+            Arc::clone(&capabilities),
+        ));
+        Self {
+            cards_client: AsyncCardClient::new(Arc::clone(&sender)),
+            decks_client: AsyncDeckClient::new(Arc::clone(&sender)),
+            media_client: AsyncMediaClient::new(Arc::clone(&sender)),
+            models_client: AsyncModelClient::new(Arc::clone(&sender)),
+            transport,
+            capabilities,
+        }
+    }
+
+    /// Gets the version of the AnkiConnect plugin
+    pub async fn version(&self) -> Result<u16, AnkiError> {
+        self.execute::<Version>(()).await
+    }
+
+    /// Gets what the connected AnkiConnect supports
+    ///
+    /// The version and the supported actions are queried with `version` and
+    /// `apiReflect` once, by this method or by the first request after AnkiConnect
+    /// answered one, and reused afterwards. Wrapper methods fail with [`AnkiError::ActionNotSupported`] instead
+    /// of sending an action the instance lacks.
+    ///
+    /// # Returns
+    ///
+    /// The capabilities of the connected AnkiConnect
+    pub async fn capabilities(&self) -> Result<Capabilities, AnkiError> {
+        capabilities::load_async(&self.capabilities, self.transport.as_ref()).await
+    }
+
+    /// Requests permission to use AnkiConnect
+    ///
+    /// This is the only action AnkiConnect accepts without an API key, and the way for
+    /// clients on other origins to ask the user for access.
+    ///
+    /// # Returns
+    ///
+    /// Whether permission was granted and, if so, whether an API key is required
+    pub async fn request_permission(&self) -> Result<Permission, AnkiError> {
+        Ok(self.execute::<RequestPermission>(()).await?.into())
+    }
+
+    /// Executes a typed action
+    ///
+    /// Use this for actions this crate does not wrap, by implementing [`Action`] for
+    /// them. The action is sent even if `apiReflect` doesn't list it.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters for the action
+    ///
+    /// # Returns
+    ///
+    /// The decoded result of the action
+    pub async fn execute<A: Action>(&self, params: A::Params) -> Result<A::Response, AnkiError> {
+        let params = action::params_to_value(params)?;
+        let summary = params.as_ref().map(redact::summarize_params);
+        self.transport
+            .send_raw(A::NAME, params)
+            .await?
+            .decode(A::NAME, summary)
+    }
+
+    /// Executes an action by name with untyped parameters
+    ///
+    /// This is an escape hatch for new or plugin-added actions, so it is sent even if
+    /// `apiReflect` doesn't list the action.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The name of the action
+    /// * `params` - The parameters for the action, a JSON object or `null` for none
+    ///
+    /// # Returns
+    ///
+    /// The raw result of the action, [`Value::Null`] if it returned nothing
+    pub async fn raw(&self, action: &str, params: Value) -> Result<Value, AnkiError> {
+        let params = action::raw_params(action, params)?;
+        let summary = params.as_ref().map(redact::summarize_params);
+        self.transport
+            .send_raw(action, params)
+            .await?
+            .into_result(action, summary)
+    }
+
+    /// Access operations related to cards and notes
+    pub fn cards(&self) -> &AsyncCardClient {
+        &self.cards_client
+    }
+
+    /// Gets an owned client for cards and notes, e.g. to move into a worker task
+    pub fn cards_owned(&self) -> AsyncCardClient {
+        self.cards_client.clone()
+    }
+
+    /// Access operations related to decks
+    pub fn decks(&self) -> &AsyncDeckClient {
+        &self.decks_client
+    }
+
+    /// Gets an owned client for decks, e.g. to move into a worker task
+    pub fn decks_owned(&self) -> AsyncDeckClient {
+        self.decks_client.clone()
+    }
+
+    /// Access operations related to media files
+    pub fn media(&self) -> &AsyncMediaClient {
+        &self.media_client
+    }
+
+    /// Gets an owned client for media files, e.g. to move into a worker task
+    pub fn media_owned(&self) -> AsyncMediaClient {
+        self.media_client.clone()
+    }
+
+    /// Access operations related to note types (models)
+    pub fn models(&self) -> &AsyncModelClient {
+        &self.models_client
+    }
+
+    /// Gets an owned client for note types (models), e.g. to move into a worker task
+    pub fn models_owned(&self) -> AsyncModelClient {
+        self.models_client.clone()
+    }
+}
+
+impl Default for AsyncAnkiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,508 @@
+//! Async client for Anki card and note operations
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::builders::{Flag, Query};
+use crate::client::cards::{
+    by_card, found, interval_history, into_cards, model_names, note_ids, prepare_note_dto,
+    SortColumn, SortDirection, CARDS_INFO_CHUNK_SIZE,
+};
+use crate::client::request::{
+    self, AddNoteParams, CardInfoDto, CardsReordering, FindCardsParams, GuiBrowseParams, InfoEntry,
+    IntervalHistoryEntry, NoteTagsDto,
+};
+use crate::client::{DuplicateScope, ForgetOptions};
+use crate::error::Result;
+use crate::http::AsyncRequestSender;
+use crate::models::{Card, CardId, Deck, DueDateSpec, Ease, EaseFactor, Interval, Note, NoteId};
+
+/// Async client for card-related operations
+#[derive(Clone)]
+pub struct AsyncCardClient {
+    sender: Arc<dyn AsyncRequestSender>,
+}
+
+impl AsyncCardClient {
+    /// Creates a new AsyncCardClient with the given request sender
+    pub(crate) fn new(sender: Arc<dyn AsyncRequestSender>) -> Self {
+        Self { sender }
+    }
+
+    /// Adds a new note to Anki.
+    ///
+    /// Note that it doesn't check validity of the fields contained in `note` and will fail
+    /// silently if `note` contains fields that are not existent in Anki.
+    ///
+    /// # Arguments
+    ///
+    /// * `deck` - The deck where the note will be added
+    /// * `note` - The note to add
+    /// * `allow_duplicate` - Whether to allow duplicate notes
+    /// * `duplicate_scope` - Optional scope for duplicate checking
+    ///
+    /// # Returns
+    ///
+    /// The ID of the created note
+    pub async fn add_note(
+        &self,
+        deck: &Deck,
+        note: Note,
+        allow_duplicate: bool,
+        duplicate_scope: Option<DuplicateScope>,
+    ) -> Result<NoteId> {
+        let note_dto = prepare_note_dto(deck, &note, allow_duplicate, duplicate_scope);
+
+        let params = AddNoteParams { note: note_dto };
+        let note_id = self.sender.send("addNote", Some(params)).await?;
+
+        Ok(NoteId(note_id))
+    }
+
+    /// Finds cards matching the given query
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query for cards
+    ///
+    /// # Returns
+    ///
+    /// A list of card IDs matching the query
+    pub async fn find(&self, query: &Query) -> Result<Vec<CardId>> {
+        let params = FindCardsParams {
+            query: query.as_str(),
+        };
+        let ids = self
+            .sender
+            .send::<_, Vec<u64>>("findCards", Some(params))
+            .await?;
+        Ok(ids.into_iter().map(CardId).collect())
+    }
+
+    /// Gets the details of the specified cards
+    ///
+    /// Large lists are split into several `cardsInfo` requests. The tags of the notes
+    /// are fetched with `notesInfo` and the note types of the cards with one additional
+    /// request.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to get
+    ///
+    /// # Returns
+    ///
+    /// The cards in the given order, leaving out cards that don't exist
+    pub async fn get_info(&self, card_ids: &[CardId]) -> Result<Vec<Card>> {
+        let mut infos = Vec::with_capacity(card_ids.len());
+        for chunk in card_ids.chunks(CARDS_INFO_CHUNK_SIZE) {
+            let params = request::CardIdsParams {
+                cards: chunk.iter().map(|id| id.0).collect(),
+            };
+            let entries = self
+                .sender
+                .send::<_, Vec<InfoEntry<CardInfoDto>>>("cardsInfo", Some(params))
+                .await?;
+            infos.extend(found(entries));
+        }
+        if infos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tags = HashMap::new();
+        for chunk in note_ids(&infos).chunks(CARDS_INFO_CHUNK_SIZE) {
+            let params = request::NoteIdsParam {
+                notes: Some(chunk.to_vec()),
+                query: None,
+            };
+            let entries = self
+                .sender
+                .send::<_, Vec<InfoEntry<NoteTagsDto>>>("notesInfo", Some(params))
+                .await?;
+            tags.extend(found(entries).map(|note| (note.note_id, note.tags)));
+        }
+
+        let model_names = model_names(&infos);
+        let params = request::FindModelsByNameParams {
+            model_names: &model_names,
+        };
+        let models = self.sender.send("findModelsByName", Some(params)).await?;
+        into_cards(infos, models, tags)
+    }
+
+    /// Opens the Anki card browser with the given query
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query for cards
+    ///
+    /// # Returns
+    ///
+    /// A list of card IDs that were found
+    pub async fn browse(&self, query: &str) -> Result<Vec<CardId>> {
+        let params = GuiBrowseParams {
+            query: query.to_string(),
+            reorder_cards: None,
+        };
+        let ids = self
+            .sender
+            .send::<_, Vec<u64>>("guiBrowse", Some(params))
+            .await?;
+        Ok(ids.into_iter().map(CardId).collect())
+    }
+
+    /// Opens the Anki card browser with the given query and sorts the results
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query for cards
+    /// * `column` - The column to sort by
+    /// * `ascending` - Whether to sort in ascending order
+    ///
+    /// # Returns
+    ///
+    /// A list of card IDs that were found
+    pub async fn browse_sorted(
+        &self,
+        query: &str,
+        column: SortColumn,
+        sort_direction: SortDirection,
+    ) -> Result<Vec<CardId>> {
+        let params = GuiBrowseParams {
+            query: query.to_string(),
+            reorder_cards: Some(CardsReordering {
+                order: sort_direction.into(),
+                column_id: column.into(),
+            }),
+        };
+
+        let ids = self
+            .sender
+            .send::<_, Vec<u64>>("guiBrowse", Some(params))
+            .await?;
+        Ok(ids.into_iter().map(CardId).collect())
+    }
+
+    /// Deletes the specified notes
+    ///
+    /// # Arguments
+    ///
+    /// * `note_ids` - The IDs of the notes to delete
+    pub async fn delete_notes(&self, note_ids: &[NoteId]) -> Result<()> {
+        let ids: Vec<u64> = note_ids.iter().map(|id| id.0).collect();
+        let params = request::DeleteNotesParams { notes: ids };
+        self.sender.send::<_, ()>("deleteNotes", Some(params)).await
+    }
+
+    /// Suspends the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to suspend
+    pub async fn suspend_cards(&self, card_ids: &[CardId]) -> Result<()> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+
+        // AnkiConnect returns whether any card changed, `are_suspended` tells the state
+        self.sender.send::<_, bool>("suspend", Some(params)).await?;
+        Ok(())
+    }
+
+    /// Unsuspends the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to unsuspend
+    pub async fn unsuspend_cards(&self, card_ids: &[CardId]) -> Result<()> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+
+        // AnkiConnect returns whether any card changed, `are_suspended` tells the state
+        self.sender
+            .send::<_, bool>("unsuspend", Some(params))
+            .await?;
+        Ok(())
+    }
+
+    /// Checks which of the specified cards are suspended
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to check
+    ///
+    /// # Returns
+    ///
+    /// Whether each card is suspended, leaving out cards that don't exist
+    pub async fn are_suspended(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, bool>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let suspended = self
+            .sender
+            .send::<_, Vec<Option<bool>>>("areSuspended", Some(params))
+            .await?;
+        Ok(by_card("areSuspended", card_ids, suspended)?
+            .filter_map(|(id, suspended)| Some((id, suspended?)))
+            .collect())
+    }
+
+    /// Checks which of the specified cards are due
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to check
+    ///
+    /// # Returns
+    ///
+    /// Whether each card is due for review or in learning
+    pub async fn are_due(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, bool>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let due = self
+            .sender
+            .send::<_, Vec<bool>>("areDue", Some(params))
+            .await?;
+        Ok(by_card("areDue", card_ids, due)?.collect())
+    }
+
+    /// Gets the current intervals of the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The current interval of each card
+    pub async fn get_intervals(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, Interval>> {
+        let params = request::GetIntervalsParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            complete: false,
+        };
+        let intervals = self
+            .sender
+            .send::<_, Vec<i64>>("getIntervals", Some(params))
+            .await?;
+        Ok(by_card("getIntervals", card_ids, intervals)?
+            .map(|(id, interval)| (id, interval.into()))
+            .collect())
+    }
+
+    /// Gets all intervals the specified cards ever had
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The intervals of each card from its review history, oldest first
+    pub async fn get_interval_history(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<Interval>>> {
+        let params = request::GetIntervalsParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            complete: true,
+        };
+        let intervals = self
+            .sender
+            .send::<_, Vec<IntervalHistoryEntry>>("getIntervals", Some(params))
+            .await?;
+        Ok(by_card("getIntervals", card_ids, intervals)?
+            .map(|(id, entry)| (id, interval_history(entry)))
+            .collect())
+    }
+
+    /// Gets the ease factors of the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The ease factor of each card, `None` for cards that were never reviewed, leaving
+    /// out cards that don't exist
+    pub async fn get_ease_factors(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Option<EaseFactor>>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let factors = self
+            .sender
+            .send::<_, Vec<Option<u32>>>("getEaseFactors", Some(params))
+            .await?;
+        Ok(by_card("getEaseFactors", card_ids, factors)?
+            .filter_map(|(id, factor)| Some((id, EaseFactor::from_anki(factor?))))
+            .collect())
+    }
+
+    /// Sets the ease factors of cards
+    ///
+    /// # Arguments
+    ///
+    /// * `ease_factors` - The cards and the ease factor to set for each of them
+    ///
+    /// # Returns
+    ///
+    /// For each card in the given order, whether its ease factor was set; `false` if
+    /// the card doesn't exist
+    pub async fn set_ease_factors(
+        &self,
+        ease_factors: &[(CardId, EaseFactor)],
+    ) -> Result<Vec<bool>> {
+        let params = request::SetEaseFactorsParams {
+            cards: ease_factors.iter().map(|(id, _)| id.0).collect(),
+            ease_factors: ease_factors
+                .iter()
+                .map(|(_, factor)| factor.value())
+                .collect(),
+        };
+        self.sender.send("setEaseFactors", Some(params)).await
+    }
+
+    /// Resets the specified cards to new cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to forget
+    /// * `options` - Whether to restore the original position and reset the counts
+    pub async fn forget_cards(&self, card_ids: &[CardId], options: ForgetOptions) -> Result<()> {
+        let params = options.to_params(card_ids);
+        self.sender.send("forgetCards", Some(params)).await
+    }
+
+    /// Moves the specified review cards back into relearning
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to relearn
+    pub async fn relearn_cards(&self, card_ids: &[CardId]) -> Result<()> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        self.sender.send("relearnCards", Some(params)).await
+    }
+
+    /// Sets when the specified cards become due, turning new cards into review cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to reschedule
+    /// * `days` - When the cards become due
+    pub async fn set_due_date(&self, card_ids: &[CardId], days: DueDateSpec) -> Result<()> {
+        let params = request::SetDueDateParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            days: days.to_string(),
+        };
+
+        // AnkiConnect always returns `true`
+        self.sender
+            .send::<_, bool>("setDueDate", Some(params))
+            .await?;
+        Ok(())
+    }
+
+    /// Answers the specified cards as if they were reviewed in Anki
+    ///
+    /// # Arguments
+    ///
+    /// * `answers` - The cards and the answer for each of them
+    ///
+    /// # Returns
+    ///
+    /// For each card in the given order, whether it was answered; `false` if the card
+    /// doesn't exist
+    pub async fn answer_cards(&self, answers: &[(CardId, Ease)]) -> Result<Vec<bool>> {
+        let params = request::AnswerCardsParams {
+            answers: answers
+                .iter()
+                .map(|(id, ease)| request::AnswerDto {
+                    card_id: id.0,
+                    ease: *ease as u8,
+                })
+                .collect(),
+        };
+        self.sender.send("answerCards", Some(params)).await
+    }
+
+    /// Sets the flag color of the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to flag
+    /// * `flag` - The flag color to set (0 = no flag, 1 = red, 2 = orange, etc.)
+    pub async fn set_flag(&self, card_ids: &[CardId], flag: Flag) -> Result<()> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::SetFlagParams {
+            cards: ids,
+            flag: flag as u8,
+        };
+
+        // AnkiConnect always returns `true`
+        self.sender.send::<_, bool>("setFlag", Some(params)).await?;
+        Ok(())
+    }
+
+    /// Gets info about the specified note
+    ///
+    /// # Arguments
+    ///
+    /// * `note_ids` - The IDs of the notes to get info for
+    ///
+    /// # Returns
+    ///
+    /// Detailed information about the note
+    pub async fn get_notes_info_by_id(
+        &self,
+        note_ids: Vec<NoteId>,
+    ) -> Result<Vec<request::NoteInfo>> {
+        let params = request::NoteIdsParam {
+            notes: Some(note_ids.iter().map(|id| id.value()).collect()),
+            query: None,
+        };
+        self.sender.send("notesInfo", Some(params)).await
+    }
+
+    /// Gets info about the notes found with the query
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to get matching notes for
+    ///
+    /// # Returns
+    ///
+    /// Detailed information about the notes that match the query
+    pub async fn get_notes_info(&self, query: &Query) -> Result<Vec<request::NoteInfo>> {
+        let params = request::NoteIdsParam {
+            notes: None,
+            query: Some(query.to_string()),
+        };
+        self.sender.send("notesInfo", Some(params)).await
+    }
+
+    pub async fn find_notes(&self, query: &Query) -> Result<Vec<NoteId>> {
+        let params = request::FindNotesParams {
+            query: query.to_string(),
+        };
+        let ids = self
+            .sender
+            .send::<_, Vec<u64>>("findNotes", Some(params))
+            .await?;
+        Ok(ids.into_iter().map(NoteId).collect())
+    }
+
+    pub async fn update_note_fields(
+        &self,
+        note_id: NoteId,
+        fields: HashMap<String, String>,
+    ) -> Result<()> {
+        let params = request::UpdateNoteFieldsParams {
+            id: note_id.value(),
+            fields,
+        };
+
+        self.sender
+            .send("updateNote", Some(HashMap::from([("note", params)])))
+            .await
+    }
+}
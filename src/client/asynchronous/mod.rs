@@ -0,0 +1,20 @@
+//! Async client interfaces for interacting with Anki through AnkiConnect
+//!
+//! This module mirrors the blocking clients in [`crate::client`] and is only
+//! available with the `async` feature. Both flavours share the same request
+//! types and domain models, so they behave the same way.
+
+// Declare submodules
+mod anki_client;
+mod cards;
+mod decks;
+mod media;
+mod models;
+
+pub use anki_client::AsyncAnkiClient;
+
+// Re-export domain-specific clients
+pub use self::cards::AsyncCardClient;
+pub use self::decks::AsyncDeckClient;
+pub use self::media::AsyncMediaClient;
+pub use self::models::AsyncModelClient;
@@ -0,0 +1,279 @@
+//! Async client for Anki model (note type) operations
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::client::request::{
+    self, FindModelsByIdParams, FindModelsByNameParams, ModelFieldNamesParams, ModelTemplatesParams,
+};
+use crate::error::{AnkiConnectError, AnkiError, Result};
+use crate::http::AsyncRequestSender;
+use crate::models::{Field, Model, ModelId, NoteId};
+
+/// Async client for model-related operations
+#[derive(Clone)]
+pub struct AsyncModelClient {
+    sender: Arc<dyn AsyncRequestSender>,
+}
+
+impl AsyncModelClient {
+    /// Creates a new AsyncModelClient with the given request sender
+    pub(crate) fn new(sender: Arc<dyn AsyncRequestSender>) -> Self {
+        Self { sender }
+    }
+
+    /// Gets all models (note types) from Anki
+    ///
+    /// # Returns
+    ///
+    /// A list of all models in the Anki collection
+    pub async fn get_all(&self) -> Result<Vec<Model>> {
+        let result: HashMap<String, u64> = self.sender.send("modelNamesAndIds", None::<()>).await?;
+        if result.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Fetch the details of all models at once instead of the fields of each model
+        // Sorted, so that repeated calls send the same request and can be cached
+        let mut ids: Vec<u64> = result.into_values().collect();
+        ids.sort_unstable();
+        let params = FindModelsByIdParams { model_ids: &ids };
+        let model_details: Vec<request::ModelDetails> =
+            self.sender.send("findModelsById", Some(params)).await?;
+
+        model_details
+            .into_iter()
+            .map(request::ModelDetails::into_model)
+            .collect()
+    }
+
+    /// Gets a model by its name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the model to get
+    ///
+    /// # Returns
+    ///
+    /// The model with the given name, if it exists
+    pub async fn get_by_name(&self, name: &str) -> Result<Option<Model>> {
+        let names = [name];
+        let params = FindModelsByNameParams {
+            model_names: &names,
+        };
+        let model_details = self.sender.send("findModelsByName", Some(params)).await;
+        first_model(model_details)
+    }
+
+    /// Gets a model by its ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the model to get
+    ///
+    /// # Returns
+    ///
+    /// The model with the given ID, if it exists
+    pub async fn get_by_id(&self, id: ModelId) -> Result<Option<Model>> {
+        let ids = [id.0];
+        let params = FindModelsByIdParams { model_ids: &ids };
+        let model_details = self.sender.send("findModelsById", Some(params)).await;
+        first_model(model_details)
+    }
+
+    /// Gets the fields for a model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to get fields for
+    ///
+    /// # Returns
+    ///
+    /// A list of fields for the model
+    pub async fn get_fields(&self, model: &Model) -> Result<Vec<Field>> {
+        Ok(model.fields().to_vec())
+    }
+
+    /// Gets the field names for a model by name
+    ///
+    /// # Arguments
+    ///
+    /// * `model_name` - The name of the model to get fields for
+    ///
+    /// # Returns
+    ///
+    /// A list of field names for the model
+    pub async fn get_fields_for_name(&self, model_name: &str) -> Result<Vec<String>> {
+        let params = ModelFieldNamesParams { model_name };
+
+        self.sender.send("modelFieldNames", Some(params)).await
+    }
+
+    /// Gets the field names for a model by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `model_id` - The ID of the model to get fields for
+    ///
+    /// # Returns
+    ///
+    /// A list of field names for the model
+    pub async fn get_fields_for_id(&self, model_id: ModelId) -> Result<Vec<String>> {
+        let model = self.get_by_id(model_id).await?.ok_or_else(|| {
+            AnkiError::ValidationError(format!("Model with ID {} not found", model_id.0))
+        })?;
+
+        self.get_fields_for_name(model.name()).await
+    }
+
+    /// Gets the template names for a model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to get templates for
+    ///
+    /// # Returns
+    ///
+    /// A list of template names for the model
+    pub async fn get_template_names(&self, model: &Model) -> Result<Vec<String>> {
+        let params = ModelTemplatesParams {
+            model_name: model.name(),
+        };
+
+        self.sender.send("modelTemplates", Some(params)).await
+    }
+
+    /// Gets the CSS styling for a model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to get styling for
+    ///
+    /// # Returns
+    ///
+    /// The CSS styling for the model
+    pub async fn get_styling(&self, model: &Model) -> Result<String> {
+        let params = request::ModelStylingParams {
+            model_name: model.name(),
+        };
+
+        self.sender.send("modelStyling", Some(params)).await
+    }
+
+    /// Creates a new model
+    ///
+    /// # Arguments
+    ///
+    /// * `model_name` - The name of the model to create
+    /// * `fields` - The field names for the model
+    /// * `css` - The CSS styling for the model
+    /// * `templates` - The templates for the model (name, front, back)
+    ///
+    /// # Returns
+    ///
+    /// The ID of the created model
+    pub async fn create_model(
+        &self,
+        model_name: &str,
+        fields: &[&str],
+        css: &str,
+        templates: &[(&str, &str, &str)],
+    ) -> Result<ModelId> {
+        if model_name.is_empty() {
+            return Err(AnkiError::ValidationError(
+                "Model name cannot be empty".to_string(),
+            ));
+        }
+
+        if fields.is_empty() {
+            return Err(AnkiError::ValidationError(
+                "Model must have at least one field".to_string(),
+            ));
+        }
+
+        if templates.is_empty() {
+            return Err(AnkiError::ValidationError(
+                "Model must have at least one template".to_string(),
+            ));
+        }
+
+        let api_templates = templates
+            .iter()
+            .map(|(name, front, back)| {
+                (
+                    name.to_string(),
+                    request::CardTemplate {
+                        front: front.to_string(),
+                        back: back.to_string(),
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        let params = request::CreateModelParams {
+            model_name,
+            in_order_fields: fields,
+            css,
+            card_templates: api_templates,
+        };
+
+        let id = self
+            .sender
+            .send::<_, u64>("createModel", Some(params))
+            .await?;
+        Ok(ModelId(id))
+    }
+
+    /// Updates the styling of a model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to update
+    /// * `css` - The new CSS styling
+    pub async fn update_styling(&self, model: &Model, css: &str) -> Result<()> {
+        let params = request::UpdateModelStylingParams {
+            model: model.name(),
+            css,
+        };
+
+        self.sender
+            .send::<_, ()>("updateModelStyling", Some(params))
+            .await
+    }
+
+    /// Gets notes that use a specific model
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to find notes for
+    ///
+    /// # Returns
+    ///
+    /// A list of note IDs that use the model
+    pub async fn find_notes_using_model(&self, model: &Model) -> Result<Vec<NoteId>> {
+        let query = format!("note:{}", model.name());
+        let params = request::FindNotesParams { query };
+
+        let ids = self
+            .sender
+            .send::<_, Vec<u64>>("findNotes", Some(params))
+            .await?;
+        Ok(ids.into_iter().map(NoteId).collect())
+    }
+}
+
+/// Gets the first of the found models, treating a missing model as `None`
+fn first_model(model_details: Result<Vec<request::ModelDetails>>) -> Result<Option<Model>> {
+    match model_details {
+        Ok(model_details) => model_details
+            .into_iter()
+            .next()
+            .map(request::ModelDetails::into_model)
+            .transpose(),
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::ModelNotFound(_),
+            ..
+        }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,226 @@
+//! Batching of several actions into a single AnkiConnect `multi` request
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{AnkiError, Result};
+use crate::http::{AnkiConnectResponse, RequestSender, API_VERSION};
+use crate::redact;
+
+use super::action::{self, Action};
+use super::request::{MultiAction, MultiParams};
+
+/// The ID of the next batch, used to tie handles to the batch they were created by
+static NEXT_BATCH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Collects actions and sends them to AnkiConnect as one `multi` request
+///
+/// Every action that is added returns a [`BatchHandle`] which is later used to
+/// fetch the typed result of that action from the [`BatchResults`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ankiconnect_rs::AnkiClient;
+/// use std::collections::HashMap;
+///
+/// # fn main() -> ankiconnect_rs::Result<()> {
+/// let client = AnkiClient::new();
+///
+/// let mut batch = client.batch();
+/// let version = batch.add::<(), u16>("version", None)?;
+/// let decks = batch.add::<(), HashMap<String, u64>>("deckNamesAndIds", None)?;
+///
+/// let results = batch.send()?;
+/// println!("AnkiConnect version: {}", results.get(&version)?);
+/// println!("Found {} decks", results.get(&decks)?.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Batch {
+    id: u64,
+    sender: Arc<dyn RequestSender>,
+    actions: Vec<MultiAction>,
+}
+
+impl Batch {
+    /// Creates a new, empty batch that sends its actions with the given request sender
+    pub(crate) fn new(sender: Arc<dyn RequestSender>) -> Self {
+        Self {
+            id: NEXT_BATCH_ID.fetch_add(1, Ordering::Relaxed),
+            sender,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Adds an action to the batch
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The name of the AnkiConnect action
+    /// * `params` - The parameters for the action
+    ///
+    /// # Returns
+    ///
+    /// A handle used to retrieve the result of this action once the batch was sent
+    pub fn add<P, R>(&mut self, action: &str, params: Option<P>) -> Result<BatchHandle<R>>
+    where
+        P: Serialize + Debug,
+        R: DeserializeOwned,
+    {
+        let params = params
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| AnkiError::JsonError(e.to_string()))?;
+
+        self.actions.push(MultiAction {
+            action: action.to_string(),
+            version: API_VERSION,
+            params,
+        });
+
+        Ok(BatchHandle {
+            batch: self.id,
+            index: self.actions.len() - 1,
+            _result: PhantomData,
+        })
+    }
+
+    /// Adds a typed action to the batch
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters for the action
+    ///
+    /// # Returns
+    ///
+    /// A handle used to retrieve the result of this action once the batch was sent
+    pub fn add_action<A: Action>(&mut self, params: A::Params) -> Result<BatchHandle<A::Response>> {
+        self.actions.push(MultiAction {
+            action: A::NAME.to_string(),
+            version: API_VERSION,
+            params: action::params_to_value(params)?,
+        });
+
+        Ok(BatchHandle {
+            batch: self.id,
+            index: self.actions.len() - 1,
+            _result: PhantomData,
+        })
+    }
+
+    /// Gets the number of actions in the batch
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if no actions were added to the batch
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Sends all actions in a single request
+    ///
+    /// Only failures of the request as a whole (e.g. connection errors) are returned
+    /// here. Errors of individual actions are reported by [`BatchResults::get`].
+    pub fn send(self) -> Result<BatchResults> {
+        if self.actions.is_empty() {
+            return Ok(BatchResults {
+                batch: self.id,
+                actions: Vec::new(),
+                responses: Vec::new(),
+            });
+        }
+
+        let params = MultiParams {
+            actions: &self.actions,
+        };
+        let responses: Vec<AnkiConnectResponse> = self.sender.send("multi", Some(params))?;
+
+        if responses.len() != self.actions.len() {
+            return Err(AnkiError::UnknownError(format!(
+                "Expected {} results from multi, got {}",
+                self.actions.len(),
+                responses.len()
+            )));
+        }
+
+        Ok(BatchResults {
+            batch: self.id,
+            actions: self.actions,
+            responses,
+        })
+    }
+}
+
+/// A typed reference to an action that was added to a [`Batch`]
+#[derive(Debug)]
+pub struct BatchHandle<R> {
+    batch: u64,
+    index: usize,
+    _result: PhantomData<fn() -> R>,
+}
+
+impl<R> BatchHandle<R> {
+    /// Gets the position of the action within its batch
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// The results of a sent [`Batch`], one per action
+#[derive(Debug)]
+pub struct BatchResults {
+    batch: u64,
+    actions: Vec<MultiAction>,
+    responses: Vec<AnkiConnectResponse>,
+}
+
+impl BatchResults {
+    /// Gets the result of a single action
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle returned when the action was added to the batch
+    ///
+    /// # Returns
+    ///
+    /// The typed result of the action, the error AnkiConnect reported for it, or a
+    /// validation error if the handle belongs to another batch
+    pub fn get<R: DeserializeOwned>(&self, handle: &BatchHandle<R>) -> Result<R> {
+        let response = self
+            .responses
+            .get(handle.index)
+            .filter(|_| handle.batch == self.batch)
+            .ok_or_else(|| {
+                AnkiError::ValidationError(format!(
+                    "Batch action {} is not part of these results",
+                    handle.index
+                ))
+            })?;
+
+        // Only summarize the parameters if they are needed for the error
+        let action = &self.actions[handle.index];
+        let summary = response
+            .error
+            .as_ref()
+            .and(action.params.as_ref())
+            .map(redact::summarize_params);
+        response.decode(&action.action, summary)
+    }
+
+    /// Gets the number of results
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns true if there are no results
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
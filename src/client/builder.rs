@@ -0,0 +1,369 @@
+//! Builder for configuring an [`AnkiClient`]
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::client::AnkiClient;
+use crate::error::{AnkiError, Result};
+use crate::http::{HttpRequestSender, RequestSender, RetryPolicy};
+use crate::limit::{RateLimit, RateLimitedSender};
+
+/// The URL AnkiConnect listens on by default
+pub const DEFAULT_URL: &str = "http://localhost:8765";
+
+/// Environment variable with the URL of AnkiConnect
+pub const URL_ENV_VAR: &str = "ANKICONNECT_URL";
+
+/// Environment variable with the API key for AnkiConnect
+pub const KEY_ENV_VAR: &str = "ANKICONNECT_KEY";
+
+/// Environment variable with the path of the config file
+#[cfg(feature = "config-file")]
+pub const CONFIG_ENV_VAR: &str = "ANKICONNECT_CONFIG";
+
+/// Builder for an [`AnkiClient`] talking to AnkiConnect over HTTP
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ankiconnect_rs::AnkiClient;
+/// use std::time::Duration;
+///
+/// # fn main() -> ankiconnect_rs::Result<()> {
+/// let client = AnkiClient::builder()
+///     .url("https://example.com/anki")
+///     .api_key("secret")
+///     .connect_timeout(Duration::from_secs(2))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AnkiClientBuilder {
+    url: String,
+    api_key: Option<String>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    cache_ttl: Option<Duration>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl AnkiClientBuilder {
+    /// Creates a builder with the default settings (AnkiConnect on localhost:8765)
+    pub fn new() -> Self {
+        Self {
+            url: DEFAULT_URL.to_string(),
+            api_key: None,
+            proxy: None,
+            user_agent: None,
+            connect_timeout: None,
+            read_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            cache_ttl: None,
+            rate_limit: None,
+        }
+    }
+
+    /// Creates a builder configured from the environment
+    ///
+    /// Reads the URL from `ANKICONNECT_URL` and the API key from `ANKICONNECT_KEY`.
+    #[cfg_attr(
+        feature = "config-file",
+        doc = "With the `config-file` feature, the config file is loaded first (see
+[`AnkiClientBuilder::discover_config_file`]) and the environment variables take
+precedence over it."
+    )]
+    #[cfg_attr(
+        not(feature = "config-file"),
+        doc = "With the `config-file` feature, the config file is loaded first and the
+environment variables take precedence over it."
+    )]
+    pub fn from_env() -> Result<Self> {
+        let mut builder = Self::new();
+        #[cfg(feature = "config-file")]
+        {
+            builder = builder.discover_config_file()?;
+        }
+
+        if let Some(url) = env_var(URL_ENV_VAR) {
+            builder = builder.url(url);
+        }
+        if let Some(api_key) = env_var(KEY_ENV_VAR) {
+            builder = builder.api_key(api_key);
+        }
+        Ok(builder)
+    }
+
+    /// Sets the full URL of AnkiConnect, including scheme and optional path
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the URL of AnkiConnect from a host and port
+    pub fn host_and_port(self, host: &str, port: u16) -> Self {
+        self.url(format!("http://{}:{}", host, port))
+    }
+
+    /// Sets the API key that is sent with every request
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sends all requests through the given HTTP or SOCKS proxy
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets how long to wait for the connection to AnkiConnect to be established
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for AnkiConnect's response once the request was sent
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the policy for retrying requests that failed in transport
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Caches deck, model and field names for the given time
+    ///
+    /// See [`AnkiClient::cached`].
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Limits how many requests are in flight and started per second
+    ///
+    /// Use this when several threads share one client, so Anki isn't swamped. See
+    /// [`RateLimitedSender`].
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Builds the HTTP request sender with the configured settings
+    ///
+    /// Use this to wrap the sender in a decorator before passing it to
+    /// [`AnkiClient::with_sender`].
+    pub fn build_sender(self) -> Result<HttpRequestSender> {
+        let url = self.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(AnkiError::ValidationError(format!(
+                "Invalid AnkiConnect URL '{}': must start with http:// or https://",
+                url
+            )));
+        }
+
+        let mut sender = HttpRequestSender::with_url(url).with_retry_policy(self.retry_policy);
+        if let Some(api_key) = self.api_key {
+            sender = sender.with_api_key(api_key);
+        }
+        if let Some(proxy) = &self.proxy {
+            sender = sender.with_proxy(proxy)?;
+        }
+        if let Some(user_agent) = self.user_agent {
+            sender = sender.with_user_agent(user_agent);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            sender = sender.with_connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            sender = sender.with_read_timeout(timeout);
+        }
+        Ok(sender)
+    }
+
+    /// Builds the client
+    ///
+    /// # Returns
+    ///
+    /// The client, or a validation error if the URL, proxy or rate limit is invalid
+    pub fn build(self) -> Result<AnkiClient> {
+        let cache_ttl = self.cache_ttl;
+        let rate_limit = self.rate_limit;
+        let mut sender: Arc<dyn RequestSender> = Arc::new(self.build_sender()?);
+        if let Some(rate_limit) = rate_limit {
+            sender = Arc::new(RateLimitedSender::new(sender, rate_limit)?);
+        }
+        let client = AnkiClient::with_sender(sender);
+        Ok(match cache_ttl {
+            Some(ttl) => client.cached(ttl),
+            None => client,
+        })
+    }
+}
+
+impl fmt::Debug for AnkiClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnkiClientBuilder")
+            .field("url", &self.url)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("proxy", &self.proxy)
+            .field("user_agent", &self.user_agent)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+impl Default for AnkiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads an environment variable, treating empty values as unset
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+#[cfg(feature = "config-file")]
+mod config_file {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use serde::Deserialize;
+
+    use super::{env_var, AnkiClientBuilder, CONFIG_ENV_VAR};
+    use crate::error::{AnkiError, Result};
+
+    /// Contents of a config file
+    ///
+    /// ```toml
+    /// url = "https://example.com/anki"
+    /// api_key = "secret"
+    /// proxy = "http://proxy:8080"
+    /// user_agent = "my-tool/1.0"
+    /// connect_timeout_ms = 2000
+    /// read_timeout_ms = 30000
+    /// max_attempts = 3
+    /// cache_ttl_ms = 60000
+    /// max_in_flight = 2
+    /// requests_per_second = 20.0
+    /// ```
+    #[derive(Deserialize, Debug, Default)]
+    #[serde(deny_unknown_fields)]
+    struct ConfigFile {
+        url: Option<String>,
+        api_key: Option<String>,
+        proxy: Option<String>,
+        user_agent: Option<String>,
+        connect_timeout_ms: Option<u64>,
+        read_timeout_ms: Option<u64>,
+        max_attempts: Option<u32>,
+        cache_ttl_ms: Option<u64>,
+        max_in_flight: Option<usize>,
+        requests_per_second: Option<f64>,
+    }
+
+    impl AnkiClientBuilder {
+        /// Applies the settings of a TOML config file
+        ///
+        /// Supported keys are `url`, `api_key`, `proxy`, `user_agent`,
+        /// `connect_timeout_ms`, `read_timeout_ms`, `max_attempts`, `cache_ttl_ms`,
+        /// `max_in_flight` and `requests_per_second`.
+        ///
+        /// # Arguments
+        ///
+        /// * `path` - The path of the config file
+        pub fn config_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+            let path = path.as_ref();
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                AnkiError::ValidationError(format!(
+                    "Could not read config file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            let config: ConfigFile = toml::from_str(&content).map_err(|e| {
+                AnkiError::ValidationError(format!("Invalid config file {}: {}", path.display(), e))
+            })?;
+
+            if let Some(url) = config.url {
+                self = self.url(url);
+            }
+            if let Some(api_key) = config.api_key {
+                self = self.api_key(api_key);
+            }
+            if let Some(proxy) = config.proxy {
+                self = self.proxy(proxy);
+            }
+            if let Some(user_agent) = config.user_agent {
+                self = self.user_agent(user_agent);
+            }
+            if let Some(ms) = config.connect_timeout_ms {
+                self = self.connect_timeout(Duration::from_millis(ms));
+            }
+            if let Some(ms) = config.read_timeout_ms {
+                self = self.read_timeout(Duration::from_millis(ms));
+            }
+            if let Some(max_attempts) = config.max_attempts {
+                self.retry_policy.max_attempts = max_attempts;
+            }
+            if let Some(ms) = config.cache_ttl_ms {
+                self = self.cache_ttl(Duration::from_millis(ms));
+            }
+            if config.max_in_flight.is_some() || config.requests_per_second.is_some() {
+                let mut rate_limit = self.rate_limit.unwrap_or_default();
+                if let Some(max_in_flight) = config.max_in_flight {
+                    rate_limit.max_in_flight = Some(max_in_flight);
+                }
+                if let Some(requests_per_second) = config.requests_per_second {
+                    rate_limit.requests_per_second = Some(requests_per_second);
+                }
+                self = self.rate_limit(rate_limit);
+            }
+            Ok(self)
+        }
+
+        /// Applies the settings of the config file, if there is one
+        ///
+        /// The file is taken from `ANKICONNECT_CONFIG`, or else from
+        /// `$XDG_CONFIG_HOME/ankiconnect/config.toml` (falling back to
+        /// `~/.config/ankiconnect/config.toml`). A missing default file is not an error.
+        pub fn discover_config_file(self) -> Result<Self> {
+            if let Some(path) = env_var(CONFIG_ENV_VAR) {
+                return self.config_file(path);
+            }
+
+            match default_config_path() {
+                Some(path) if path.is_file() => self.config_file(path),
+                _ => Ok(self),
+            }
+        }
+    }
+
+    fn default_config_path() -> Option<PathBuf> {
+        let config_dir = env_var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env_var("HOME").map(|home| Path::new(&home).join(".config")))
+            .or_else(|| env_var("APPDATA").map(PathBuf::from))?;
+        Some(config_dir.join("ankiconnect").join("config.toml"))
+    }
+}
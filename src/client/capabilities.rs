@@ -0,0 +1,278 @@
+//! Checking actions against the capabilities of the connected AnkiConnect
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::client::action::{Action, ApiReflect, Version};
+use crate::client::request::{ApiReflectDto, ApiReflectParams};
+use crate::error::{AnkiError, Result};
+use crate::http::{AnkiConnectResponse, RequestSender};
+#[cfg(feature = "async")]
+use crate::http::{AsyncRequestSender, BoxFuture};
+use crate::models::Capabilities;
+use crate::AnkiConnectError;
+
+/// The capabilities of a connection, loaded on first use and shared by all clients
+pub(crate) type SharedCapabilities = Arc<CapabilityState>;
+
+/// How long checked requests are sent without loading the capabilities after loading
+/// them failed
+const LOAD_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The loaded capabilities, or when loading them last failed
+#[derive(Default)]
+pub(crate) struct CapabilityState {
+    loaded: OnceLock<Capabilities>,
+    /// Whether AnkiConnect answered a checked request
+    answered: AtomicBool,
+    failed_at: Mutex<Option<Instant>>,
+}
+
+impl CapabilityState {
+    /// Gets the capabilities if they were loaded
+    fn get(&self) -> Option<&Capabilities> {
+        self.loaded.get()
+    }
+
+    /// Returns false until AnkiConnect answered, and while loading the capabilities
+    /// failed less than [`LOAD_RETRY_INTERVAL`] ago
+    fn may_load(&self) -> bool {
+        let failed_at = *self.failed_at();
+        self.answered.load(Ordering::Relaxed)
+            && failed_at.is_none_or(|failed_at| failed_at.elapsed() >= LOAD_RETRY_INTERVAL)
+    }
+
+    /// Remembers that AnkiConnect answered, if the request got a response
+    fn note_answer(&self, response: &Result<AnkiConnectResponse>) {
+        if response.is_ok() {
+            self.answered.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Stores the loaded capabilities, or remembers when loading them failed
+    fn store(&self, loaded: Result<Capabilities>) -> Result<Capabilities> {
+        match loaded {
+            Ok(capabilities) => Ok(self.loaded.get_or_init(|| capabilities).clone()),
+            Err(error) => {
+                *self.failed_at() = Some(Instant::now());
+                Err(error)
+            }
+        }
+    }
+
+    fn failed_at(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.failed_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Actions that are sent without loading the capabilities first
+///
+/// `requestPermission` has to work before AnkiConnect accepts anything else.
+const UNCHECKED_ACTIONS: &[&str] = &[Version::NAME, ApiReflect::NAME, "requestPermission"];
+
+/// Decorator that rejects actions the connected AnkiConnect does not support
+///
+/// The capabilities are loaded before the first checked request after AnkiConnect
+/// answered one, so requests to an Anki that isn't running don't wait for the retries
+/// of `version` as well. Until then requests are sent unchecked, and so are the
+/// requests of the next [`LOAD_RETRY_INTERVAL`] after loading failed.
+pub(crate) struct CapabilityCheck {
+    inner: Arc<dyn RequestSender>,
+    capabilities: SharedCapabilities,
+}
+
+impl CapabilityCheck {
+    pub(crate) fn new(inner: Arc<dyn RequestSender>, capabilities: SharedCapabilities) -> Self {
+        Self {
+            inner,
+            capabilities,
+        }
+    }
+}
+
+impl RequestSender for CapabilityCheck {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        if !UNCHECKED_ACTIONS.contains(&action) {
+            let capabilities = match self.capabilities.get() {
+                Some(capabilities) => Some(capabilities.clone()),
+                None if self.capabilities.may_load() => {
+                    load(&self.capabilities, self.inner.as_ref()).ok()
+                }
+                None => None,
+            };
+            check(capabilities.as_ref(), action, params.as_ref())?;
+        }
+        let response = self.inner.send_raw(action, params);
+        self.capabilities.note_answer(&response);
+        response
+    }
+}
+
+/// Async counterpart of [`CapabilityCheck`]
+#[cfg(feature = "async")]
+pub(crate) struct AsyncCapabilityCheck {
+    inner: Arc<dyn AsyncRequestSender>,
+    capabilities: SharedCapabilities,
+}
+
+#[cfg(feature = "async")]
+impl AsyncCapabilityCheck {
+    pub(crate) fn new(
+        inner: Arc<dyn AsyncRequestSender>,
+        capabilities: SharedCapabilities,
+    ) -> Self {
+        Self {
+            inner,
+            capabilities,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncRequestSender for AsyncCapabilityCheck {
+    fn send_raw<'a>(
+        &'a self,
+        action: &'a str,
+        params: Option<Value>,
+    ) -> BoxFuture<'a, Result<AnkiConnectResponse>> {
+        Box::pin(async move {
+            if !UNCHECKED_ACTIONS.contains(&action) {
+                let capabilities = match self.capabilities.get() {
+                    Some(capabilities) => Some(capabilities.clone()),
+                    None if self.capabilities.may_load() => {
+                        load_async(&self.capabilities, self.inner.as_ref())
+                            .await
+                            .ok()
+                    }
+                    None => None,
+                };
+                check(capabilities.as_ref(), action, params.as_ref())?;
+            }
+            let response = self.inner.send_raw(action, params).await;
+            self.capabilities.note_answer(&response);
+            response
+        })
+    }
+}
+
+/// Gets the capabilities, loading them with `version` and `apiReflect` until that
+/// succeeds
+pub(crate) fn load(
+    capabilities: &SharedCapabilities,
+    sender: &dyn RequestSender,
+) -> Result<Capabilities> {
+    if let Some(capabilities) = capabilities.get() {
+        return Ok(capabilities.clone());
+    }
+    capabilities.store(fetch(sender))
+}
+
+/// Queries the capabilities with `version` and `apiReflect`
+fn fetch(sender: &dyn RequestSender) -> Result<Capabilities> {
+    let version = sender
+        .send_raw(Version::NAME, None)?
+        .decode(Version::NAME, None)?;
+    let actions = reflected_actions(
+        sender
+            .send_raw(ApiReflect::NAME, Some(reflect_params()?))?
+            .decode(ApiReflect::NAME, None),
+    )?;
+
+    Ok(Capabilities::new(version, actions))
+}
+
+/// Async counterpart of [`load`]
+#[cfg(feature = "async")]
+pub(crate) async fn load_async(
+    capabilities: &SharedCapabilities,
+    sender: &dyn AsyncRequestSender,
+) -> Result<Capabilities> {
+    if let Some(capabilities) = capabilities.get() {
+        return Ok(capabilities.clone());
+    }
+    capabilities.store(fetch_async(sender).await)
+}
+
+/// Async counterpart of [`fetch`]
+#[cfg(feature = "async")]
+async fn fetch_async(sender: &dyn AsyncRequestSender) -> Result<Capabilities> {
+    let version = sender
+        .send_raw(Version::NAME, None)
+        .await?
+        .decode(Version::NAME, None)?;
+    let actions = reflected_actions(
+        sender
+            .send_raw(ApiReflect::NAME, Some(reflect_params()?))
+            .await?
+            .decode(ApiReflect::NAME, None),
+    )?;
+
+    Ok(Capabilities::new(version, actions))
+}
+
+/// Checks an action, or every action of a `multi` request, against the capabilities
+pub(crate) fn check(
+    capabilities: Option<&Capabilities>,
+    action: &str,
+    params: Option<&Value>,
+) -> Result<()> {
+    let Some(capabilities) = capabilities else {
+        return Ok(());
+    };
+
+    if action == "multi" {
+        let actions = params
+            .and_then(|params| params.get("actions"))
+            .and_then(Value::as_array);
+        for inner in actions.into_iter().flatten() {
+            if let Some(action) = inner.get("action").and_then(Value::as_str) {
+                check(Some(capabilities), action, inner.get("params"))?;
+            }
+        }
+        return Ok(());
+    }
+
+    if capabilities.supports(action) {
+        Ok(())
+    } else {
+        Err(AnkiError::ActionNotSupported {
+            action: action.to_string(),
+            version: capabilities.version(),
+        })
+    }
+}
+
+/// Parameters of `apiReflect` asking for all supported actions
+pub(crate) fn reflect_params() -> Result<Value> {
+    serde_json::to_value(ApiReflectParams {
+        scopes: vec!["actions".to_string()],
+        actions: None,
+    })
+    .map_err(|e| AnkiError::JsonError(e.to_string()))
+}
+
+/// Gets the supported actions from the result of `apiReflect`
+///
+/// Releases without `apiReflect` can't list their actions, which is reported as `None`.
+pub(crate) fn reflected_actions(
+    reflected: Result<ApiReflectDto>,
+) -> Result<Option<BTreeSet<String>>> {
+    match reflected {
+        Ok(reflected) => Ok(Some(reflected.actions.into_iter().collect())),
+        Err(error)
+            if matches!(
+                error.anki_connect_error(),
+                Some(AnkiConnectError::UnsupportedAction)
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(error) => Err(error),
+    }
+}
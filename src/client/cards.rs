@@ -1,34 +1,34 @@
 //! Client for Anki card and note operations
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use crate::builders::{Flag, Query};
-use crate::error::Result;
-use crate::http::{HttpRequestSender, RequestSender};
-use crate::models::{CardId, Deck, Note, NoteId};
+use crate::error::{AnkiError, Result};
+use crate::http::{self, RequestSender};
+use crate::models::{Card, CardId, Deck, DueDateSpec, Ease, EaseFactor, Interval, Note, NoteId};
 
 use super::request::{
-    self, AddNoteOptions, AddNoteParams, CardsReordering, DuplicateScopeDto, FindCardsParams,
-    GuiBrowseParams, Media, NoteDto,
+    self, AddNoteOptions, AddNoteParams, CardInfoDto, CardsReordering, DuplicateScopeDto,
+    FindCardsParams, GuiBrowseParams, InfoEntry, IntervalHistoryEntry, Media, ModelDetails,
+    NoteDto, NoteTagsDto,
 };
 
+/// The maximum number of cards or notes requested with a single `cardsInfo` or `notesInfo`
+pub(super) const CARDS_INFO_CHUNK_SIZE: usize = 500;
+
 /// Client for card-related operations
+#[derive(Clone)]
 pub struct CardClient {
-    sender: Arc<HttpRequestSender>,
+    sender: Arc<dyn RequestSender>,
 }
 
 impl CardClient {
     /// Creates a new CardClient with the given request sender
-    pub(crate) fn new(sender: Arc<HttpRequestSender>) -> Self {
+    pub(crate) fn new(sender: Arc<dyn RequestSender>) -> Self {
         Self { sender }
     }
 
-    /// Gets the version of the AnkiConnect plugin
-    pub(crate) fn get_version(&self) -> Result<u16> {
-        self.sender.send::<(), u16>("version", None)
-    }
-
     /// Adds a new note to Anki.
     ///
     /// Note that it doesn't check validity of the fields contained in `note` and will fail
@@ -53,7 +53,7 @@ impl CardClient {
     ) -> Result<NoteId> {
         // TODO: Probably add a validity check for missing fields
         // Convert the domain note to the API format
-        let note_dto = self.prepare_note_dto(deck, &note, allow_duplicate, duplicate_scope);
+        let note_dto = prepare_note_dto(deck, &note, allow_duplicate, duplicate_scope);
 
         // Send the request to add the note
         let params = AddNoteParams { note: note_dto };
@@ -79,6 +79,54 @@ impl CardClient {
         Ok(ids.into_iter().map(CardId).collect())
     }
 
+    /// Gets the details of the specified cards
+    ///
+    /// Large lists are split into several `cardsInfo` requests. The tags of the notes
+    /// are fetched with `notesInfo` and the note types of the cards with one additional
+    /// request.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to get
+    ///
+    /// # Returns
+    ///
+    /// The cards in the given order, leaving out cards that don't exist
+    pub fn get_info(&self, card_ids: &[CardId]) -> Result<Vec<Card>> {
+        let mut infos = Vec::with_capacity(card_ids.len());
+        for chunk in card_ids.chunks(CARDS_INFO_CHUNK_SIZE) {
+            let params = request::CardIdsParams {
+                cards: chunk.iter().map(|id| id.0).collect(),
+            };
+            let entries = self
+                .sender
+                .send::<_, Vec<InfoEntry<CardInfoDto>>>("cardsInfo", Some(params))?;
+            infos.extend(found(entries));
+        }
+        if infos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tags = HashMap::new();
+        for chunk in note_ids(&infos).chunks(CARDS_INFO_CHUNK_SIZE) {
+            let params = request::NoteIdsParam {
+                notes: Some(chunk.to_vec()),
+                query: None,
+            };
+            let entries = self
+                .sender
+                .send::<_, Vec<InfoEntry<NoteTagsDto>>>("notesInfo", Some(params))?;
+            tags.extend(found(entries).map(|note| (note.note_id, note.tags)));
+        }
+
+        let model_names = model_names(&infos);
+        let params = request::FindModelsByNameParams {
+            model_names: &model_names,
+        };
+        let models = self.sender.send("findModelsByName", Some(params))?;
+        into_cards(infos, models, tags)
+    }
+
     /// Opens the Anki card browser with the given query
     ///
     /// # Arguments
@@ -145,7 +193,10 @@ impl CardClient {
     pub fn suspend_cards(&self, card_ids: &[CardId]) -> Result<()> {
         let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
         let params = request::CardIdsParams { cards: ids };
-        self.sender.send::<_, ()>("suspend", Some(params))
+
+        // AnkiConnect returns whether any card changed, `are_suspended` tells the state
+        self.sender.send::<_, bool>("suspend", Some(params))?;
+        Ok(())
     }
 
     /// Unsuspends the specified cards
@@ -156,7 +207,200 @@ impl CardClient {
     pub fn unsuspend_cards(&self, card_ids: &[CardId]) -> Result<()> {
         let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
         let params = request::CardIdsParams { cards: ids };
-        self.sender.send::<_, ()>("unsuspend", Some(params))
+
+        // AnkiConnect returns whether any card changed, `are_suspended` tells the state
+        self.sender.send::<_, bool>("unsuspend", Some(params))?;
+        Ok(())
+    }
+
+    /// Checks which of the specified cards are suspended
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to check
+    ///
+    /// # Returns
+    ///
+    /// Whether each card is suspended, leaving out cards that don't exist
+    pub fn are_suspended(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, bool>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let suspended = self
+            .sender
+            .send::<_, Vec<Option<bool>>>("areSuspended", Some(params))?;
+        Ok(by_card("areSuspended", card_ids, suspended)?
+            .filter_map(|(id, suspended)| Some((id, suspended?)))
+            .collect())
+    }
+
+    /// Checks which of the specified cards are due
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to check
+    ///
+    /// # Returns
+    ///
+    /// Whether each card is due for review or in learning
+    pub fn are_due(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, bool>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let due = self.sender.send::<_, Vec<bool>>("areDue", Some(params))?;
+        Ok(by_card("areDue", card_ids, due)?.collect())
+    }
+
+    /// Gets the current intervals of the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The current interval of each card
+    pub fn get_intervals(&self, card_ids: &[CardId]) -> Result<HashMap<CardId, Interval>> {
+        let params = request::GetIntervalsParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            complete: false,
+        };
+        let intervals = self
+            .sender
+            .send::<_, Vec<i64>>("getIntervals", Some(params))?;
+        Ok(by_card("getIntervals", card_ids, intervals)?
+            .map(|(id, interval)| (id, interval.into()))
+            .collect())
+    }
+
+    /// Gets all intervals the specified cards ever had
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The intervals of each card from its review history, oldest first
+    pub fn get_interval_history(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<Interval>>> {
+        let params = request::GetIntervalsParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            complete: true,
+        };
+        let intervals = self
+            .sender
+            .send::<_, Vec<IntervalHistoryEntry>>("getIntervals", Some(params))?;
+        Ok(by_card("getIntervals", card_ids, intervals)?
+            .map(|(id, entry)| (id, interval_history(entry)))
+            .collect())
+    }
+
+    /// Gets the ease factors of the specified cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards
+    ///
+    /// # Returns
+    ///
+    /// The ease factor of each card, `None` for cards that were never reviewed, leaving
+    /// out cards that don't exist
+    pub fn get_ease_factors(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Option<EaseFactor>>> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        let factors = self
+            .sender
+            .send::<_, Vec<Option<u32>>>("getEaseFactors", Some(params))?;
+        Ok(by_card("getEaseFactors", card_ids, factors)?
+            .filter_map(|(id, factor)| Some((id, EaseFactor::from_anki(factor?))))
+            .collect())
+    }
+
+    /// Sets the ease factors of cards
+    ///
+    /// # Arguments
+    ///
+    /// * `ease_factors` - The cards and the ease factor to set for each of them
+    ///
+    /// # Returns
+    ///
+    /// For each card in the given order, whether its ease factor was set; `false` if
+    /// the card doesn't exist
+    pub fn set_ease_factors(&self, ease_factors: &[(CardId, EaseFactor)]) -> Result<Vec<bool>> {
+        let params = request::SetEaseFactorsParams {
+            cards: ease_factors.iter().map(|(id, _)| id.0).collect(),
+            ease_factors: ease_factors
+                .iter()
+                .map(|(_, factor)| factor.value())
+                .collect(),
+        };
+        self.sender.send("setEaseFactors", Some(params))
+    }
+
+    /// Resets the specified cards to new cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to forget
+    /// * `options` - Whether to restore the original position and reset the counts
+    pub fn forget_cards(&self, card_ids: &[CardId], options: ForgetOptions) -> Result<()> {
+        let params = options.to_params(card_ids);
+        self.sender.send("forgetCards", Some(params))
+    }
+
+    /// Moves the specified review cards back into relearning
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to relearn
+    pub fn relearn_cards(&self, card_ids: &[CardId]) -> Result<()> {
+        let ids: Vec<u64> = card_ids.iter().map(|id| id.0).collect();
+        let params = request::CardIdsParams { cards: ids };
+        self.sender.send("relearnCards", Some(params))
+    }
+
+    /// Sets when the specified cards become due, turning new cards into review cards
+    ///
+    /// # Arguments
+    ///
+    /// * `card_ids` - The IDs of the cards to reschedule
+    /// * `days` - When the cards become due
+    pub fn set_due_date(&self, card_ids: &[CardId], days: DueDateSpec) -> Result<()> {
+        let params = request::SetDueDateParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            days: days.to_string(),
+        };
+
+        // AnkiConnect always returns `true`
+        self.sender.send::<_, bool>("setDueDate", Some(params))?;
+        Ok(())
+    }
+
+    /// Answers the specified cards as if they were reviewed in Anki
+    ///
+    /// # Arguments
+    ///
+    /// * `answers` - The cards and the answer for each of them
+    ///
+    /// # Returns
+    ///
+    /// For each card in the given order, whether it was answered; `false` if the card
+    /// doesn't exist
+    pub fn answer_cards(&self, answers: &[(CardId, Ease)]) -> Result<Vec<bool>> {
+        let params = request::AnswerCardsParams {
+            answers: answers
+                .iter()
+                .map(|(id, ease)| request::AnswerDto {
+                    card_id: id.0,
+                    ease: *ease as u8,
+                })
+                .collect(),
+        };
+        self.sender.send("answerCards", Some(params))
     }
 
     /// Sets the flag color of the specified cards
@@ -172,7 +416,9 @@ impl CardClient {
             flag: flag as u8,
         };
 
-        self.sender.send::<_, ()>("setFlag", Some(params))
+        // AnkiConnect always returns `true`
+        self.sender.send::<_, bool>("setFlag", Some(params))?;
+        Ok(())
     }
 
     /// Gets info about the specified note
@@ -230,60 +476,137 @@ impl CardClient {
         self.sender
             .send("updateNote", Some(HashMap::from([("note", params)])))
     }
+}
 
-    /// Converts a domain note to a NoteDto for the API
-    fn prepare_note_dto(
-        &self,
-        deck: &Deck,
-        note: &Note,
-        allow_duplicate: bool,
-        duplicate_scope: Option<DuplicateScope>,
-    ) -> NoteDto {
-        // Prepare media
-        let mut audio = Vec::new();
-        let mut video = Vec::new();
-        let mut picture = Vec::new();
-
-        for field_media in note.media() {
-            let media = Media {
-                path: field_media.media.source().path().map(|p| p.to_path_buf()),
-                url: field_media.media.source().url().map(|u| u.to_string()),
-                data: field_media.media.source().data().map(|d| d.to_string()),
-                filename: field_media.media.filename().to_string(),
-                fields: vec![field_media.field.clone()],
-            };
+/// Pairs the per-card results of an action with the IDs of the cards
+///
+/// Fails if the action didn't return exactly one result per card.
+pub(super) fn by_card<'a, T: 'a>(
+    action: &str,
+    card_ids: &'a [CardId],
+    results: Vec<T>,
+) -> Result<impl Iterator<Item = (CardId, T)> + 'a> {
+    if results.len() != card_ids.len() {
+        return Err(AnkiError::JsonError(format!(
+            "Invalid result of '{}': expected {} entries, got {}",
+            action,
+            card_ids.len(),
+            results.len()
+        )));
+    }
+    Ok(card_ids.iter().copied().zip(results))
+}
+
+/// Drops the entries of cards or notes that don't exist
+pub(super) fn found<T>(entries: Vec<InfoEntry<T>>) -> impl Iterator<Item = T> {
+    entries.into_iter().filter_map(|entry| match entry {
+        InfoEntry::Found(info) => Some(info),
+        InfoEntry::Missing => None,
+    })
+}
 
-            match field_media.media.media_type() {
-                crate::models::MediaType::Audio => audio.push(media),
-                crate::models::MediaType::Video => video.push(media),
-                crate::models::MediaType::Image => picture.push(media),
-            }
+/// Converts the interval history of a card, which is a plain number for new cards
+pub(super) fn interval_history(entry: IntervalHistoryEntry) -> Vec<Interval> {
+    match entry {
+        IntervalHistoryEntry::History(intervals) => {
+            intervals.into_iter().map(Interval::from).collect()
         }
+        IntervalHistoryEntry::Unreviewed(0) => Vec::new(),
+        IntervalHistoryEntry::Unreviewed(interval) => vec![interval.into()],
+    }
+}
+
+/// Gets the distinct note types of the cards
+pub(super) fn model_names(infos: &[CardInfoDto]) -> Vec<&str> {
+    let names: BTreeSet<&str> = infos.iter().map(|info| info.model_name.as_str()).collect();
+    names.into_iter().collect()
+}
 
-        // Configure duplicate handling
-        let duplicate_scope_options = if let Some(_scope) = &duplicate_scope {
-            // TODO: Not implemented yet
-            None
-        } else {
-            None
+/// Gets the distinct notes of the cards
+pub(super) fn note_ids(infos: &[CardInfoDto]) -> Vec<u64> {
+    let ids: BTreeSet<u64> = infos.iter().map(|info| info.note).collect();
+    ids.into_iter().collect()
+}
+
+/// Combines the card infos with the details of their note types and the tags of their
+/// notes
+pub(super) fn into_cards(
+    infos: Vec<CardInfoDto>,
+    models: Vec<ModelDetails>,
+    tags: HashMap<u64, Vec<String>>,
+) -> Result<Vec<Card>> {
+    let models = models
+        .into_iter()
+        .map(|details| Ok((details.name.clone(), details.into_model()?)))
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    infos
+        .into_iter()
+        .map(|info| {
+            let model = models.get(&info.model_name).cloned().ok_or_else(|| {
+                http::action_failed(
+                    "findModelsByName",
+                    None,
+                    format!("model was not found: {}", info.model_name),
+                )
+            })?;
+            let tags = tags.get(&info.note).cloned().unwrap_or_default();
+            Ok(Card::from_info(info, model, tags))
+        })
+        .collect()
+}
+
+/// Converts a domain note to a NoteDto for the API
+pub(super) fn prepare_note_dto(
+    deck: &Deck,
+    note: &Note,
+    allow_duplicate: bool,
+    duplicate_scope: Option<DuplicateScope>,
+) -> NoteDto {
+    // Prepare media
+    let mut audio = Vec::new();
+    let mut video = Vec::new();
+    let mut picture = Vec::new();
+
+    for field_media in note.media() {
+        let media = Media {
+            path: field_media.media.source().path().map(|p| p.to_path_buf()),
+            url: field_media.media.source().url().map(|u| u.to_string()),
+            data: field_media.media.source().data().map(|d| d.to_string()),
+            filename: field_media.media.filename().to_string(),
+            fields: vec![field_media.field.clone()],
         };
 
-        // Create the note DTO
-        NoteDto {
-            deck_name: deck.name().to_string(),
-            model_name: note.model().name().to_string(),
-            fields: note.field_values().clone(),
-            options: AddNoteOptions {
-                allow_duplicate,
-                duplicate_scope: duplicate_scope.map(|ds| ds.into()),
-                duplicate_scope_options,
-            },
-            tags: note.tags().iter().cloned().collect(),
-            audio,
-            video,
-            picture,
+        match field_media.media.media_type() {
+            crate::models::MediaType::Audio => audio.push(media),
+            crate::models::MediaType::Video => video.push(media),
+            crate::models::MediaType::Image => picture.push(media),
         }
     }
+
+    // Configure duplicate handling
+    let duplicate_scope_options = if let Some(_scope) = &duplicate_scope {
+        // TODO: Not implemented yet
+        None
+    } else {
+        None
+    };
+
+    // Create the note DTO
+    NoteDto {
+        deck_name: deck.name().to_string(),
+        model_name: note.model().name().to_string(),
+        fields: note.field_values().clone(),
+        options: AddNoteOptions {
+            allow_duplicate,
+            duplicate_scope: duplicate_scope.map(|ds| ds.into()),
+            duplicate_scope_options,
+        },
+        tags: note.tags().iter().cloned().collect(),
+        audio,
+        video,
+        picture,
+    }
 }
 
 /// Controls how duplicate notes are detected when adding new notes.
@@ -305,6 +628,37 @@ impl From<DuplicateScope> for DuplicateScopeDto {
     }
 }
 
+/// Options for forgetting cards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForgetOptions {
+    /// Put new cards back at their original position in the new queue
+    pub restore_position: bool,
+    /// Reset the review and lapse counts to zero
+    pub reset_counts: bool,
+}
+
+impl ForgetOptions {
+    pub(super) fn to_params(self, card_ids: &[CardId]) -> request::ForgetCardsParams {
+        let defaults = Self::default();
+        request::ForgetCardsParams {
+            cards: card_ids.iter().map(|id| id.0).collect(),
+            restore_position: (self.restore_position != defaults.restore_position)
+                .then_some(self.restore_position),
+            reset_counts: (self.reset_counts != defaults.reset_counts).then_some(self.reset_counts),
+        }
+    }
+}
+
+impl Default for ForgetOptions {
+    /// Restores the position but keeps the counts, like AnkiConnect does
+    fn default() -> Self {
+        Self {
+            restore_position: true,
+            reset_counts: false,
+        }
+    }
+}
+
 /// Columns that can be used for sorting in the card browser
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortColumn {
@@ -2,20 +2,21 @@
 
 use super::request::{self, CreateDeckParams, DeckConfigsResult, DeckTreeNode};
 use crate::error::{AnkiError, Result};
-use crate::http::{HttpRequestSender, RequestSender};
+use crate::http::RequestSender;
 use crate::models::{CardId, Deck, DeckConfig, DeckId, DeckStats};
 use crate::QueryBuilder;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Client for deck-related operations
+#[derive(Clone)]
 pub struct DeckClient {
-    sender: Arc<HttpRequestSender>,
+    sender: Arc<dyn RequestSender>,
 }
 
 impl DeckClient {
     /// Creates a new DeckClient with the given request sender
-    pub(crate) fn new(sender: Arc<HttpRequestSender>) -> Self {
+    pub(crate) fn new(sender: Arc<dyn RequestSender>) -> Self {
         Self { sender }
     }
 
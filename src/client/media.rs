@@ -4,19 +4,20 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::error::{AnkiError, Result};
-use crate::http::{HttpRequestSender, RequestSender};
+use crate::http::RequestSender;
 use crate::models::MediaSource;
 
 use super::request::{self, StoreMediaFileParams};
 
 /// Client for media-related operations
+#[derive(Clone)]
 pub struct MediaClient {
-    sender: Arc<HttpRequestSender>,
+    sender: Arc<dyn RequestSender>,
 }
 
 impl MediaClient {
     /// Creates a new MediaClient with the given request sender
-    pub(crate) fn new(sender: Arc<HttpRequestSender>) -> Self {
+    pub(crate) fn new(sender: Arc<dyn RequestSender>) -> Self {
         Self { sender }
     }
 
@@ -4,18 +4,26 @@
 //! the Anki application - cards, decks, media, and models.
 
 // Declare submodules
+pub mod action;
 mod anki_client;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+mod batch;
+mod builder;
+mod capabilities;
 mod cards;
 mod decks;
 mod media;
 mod models;
 pub mod request;
 
-pub use self::cards::DuplicateScope;
+pub use self::cards::{DuplicateScope, ForgetOptions};
 pub use anki_client::AnkiClient;
+pub use batch::{Batch, BatchHandle, BatchResults};
+pub use builder::AnkiClientBuilder;
 
 // Re-export domain-specific clients
-pub(crate) use self::cards::CardClient;
-pub(crate) use self::decks::DeckClient;
-pub(crate) use self::media::MediaClient;
-pub(crate) use self::models::ModelClient;
+pub use self::cards::CardClient;
+pub use self::decks::DeckClient;
+pub use self::media::MediaClient;
+pub use self::models::ModelClient;
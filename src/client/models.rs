@@ -3,20 +3,23 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::error::{AnkiError, Result};
-use crate::http::{HttpRequestSender, RequestSender};
+use crate::error::{AnkiConnectError, AnkiError, Result};
+use crate::http::RequestSender;
 use crate::models::{Field, Model, ModelId, NoteId};
 
-use super::request::{self, FindModelsByIdParams, ModelFieldNamesParams, ModelTemplatesParams};
+use super::request::{
+    self, FindModelsByIdParams, FindModelsByNameParams, ModelFieldNamesParams, ModelTemplatesParams,
+};
 
 /// Client for model-related operations
+#[derive(Clone)]
 pub struct ModelClient {
-    sender: Arc<HttpRequestSender>,
+    sender: Arc<dyn RequestSender>,
 }
 
 impl ModelClient {
     /// Creates a new ModelClient with the given request sender
-    pub(crate) fn new(sender: Arc<HttpRequestSender>) -> Self {
+    pub(crate) fn new(sender: Arc<dyn RequestSender>) -> Self {
         Self { sender }
     }
 
@@ -27,24 +30,22 @@ impl ModelClient {
     /// A list of all models in the Anki collection
     pub fn get_all(&self) -> Result<Vec<Model>> {
         let result: HashMap<String, u64> = self.sender.send("modelNamesAndIds", None::<()>)?;
-
-        // For each model, fetch its fields
-        let mut models = Vec::with_capacity(result.len());
-        for (name, id) in result {
-            let fields = self.get_fields_for_name(&name)?;
-
-            models.push(Model::new(
-                id,
-                name,
-                fields
-                    .into_iter()
-                    .enumerate()
-                    .map(|(ord, name)| Field::new(name, ord))
-                    .collect(),
-            )?);
+        if result.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(models)
+        // Fetch the details of all models at once instead of the fields of each model
+        // Sorted, so that repeated calls send the same request and can be cached
+        let mut ids: Vec<u64> = result.into_values().collect();
+        ids.sort_unstable();
+        let params = FindModelsByIdParams { model_ids: &ids };
+        let model_details: Vec<request::ModelDetails> =
+            self.sender.send("findModelsById", Some(params))?;
+
+        model_details
+            .into_iter()
+            .map(request::ModelDetails::into_model)
+            .collect()
     }
 
     /// Gets a model by its name
@@ -57,8 +58,12 @@ impl ModelClient {
     ///
     /// The model with the given name, if it exists
     pub fn get_by_name(&self, name: &str) -> Result<Option<Model>> {
-        let models = self.get_all()?;
-        Ok(models.into_iter().find(|m| m.name() == name))
+        let names = [name];
+        let params = FindModelsByNameParams {
+            model_names: &names,
+        };
+        let model_details = self.sender.send("findModelsByName", Some(params));
+        first_model(model_details)
     }
 
     /// Gets a model by its ID
@@ -73,28 +78,8 @@ impl ModelClient {
     pub fn get_by_id(&self, id: ModelId) -> Result<Option<Model>> {
         let ids = [id.0];
         let params = FindModelsByIdParams { model_ids: &ids };
-
-        let model_details: Vec<request::ModelDetails> =
-            self.sender.send("findModelsById", Some(params))?;
-
-        if model_details.is_empty() {
-            return Ok(None);
-        }
-
-        let model_detail = &model_details[0];
-
-        // Extract fields from the model details
-        let fields = model_detail
-            .flds
-            .iter()
-            .map(|f| Field::new(f.name.clone(), f.ord as usize))
-            .collect::<Vec<_>>();
-
-        Ok(Some(Model::new(
-            model_detail.id,
-            model_detail.name.clone(),
-            fields,
-        )?))
+        let model_details = self.sender.send("findModelsById", Some(params));
+        first_model(model_details)
     }
 
     /// Gets the fields for a model
@@ -275,3 +260,19 @@ impl ModelClient {
         Ok(ids.into_iter().map(NoteId).collect())
     }
 }
+
+/// Gets the first of the found models, treating a missing model as `None`
+fn first_model(model_details: Result<Vec<request::ModelDetails>>) -> Result<Option<Model>> {
+    match model_details {
+        Ok(model_details) => model_details
+            .into_iter()
+            .next()
+            .map(request::ModelDetails::into_model)
+            .transpose(),
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::ModelNotFound(_),
+            ..
+        }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! These are internal types used for serializing requests to and from AnkiConnect.
 
-use serde::{Deserialize, Serialize};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::error::Result;
+use crate::models::{Field as ModelField, Model};
+
 // -------------------
 // Card-related params
 // -------------------
@@ -51,6 +55,7 @@ pub(crate) enum SortOrder {
 /// Column identifier for sorting
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
 pub(crate) enum ColumnIdentifier {
     #[serde(rename = "")]
     Custom,
@@ -97,6 +102,118 @@ pub(crate) struct CardIdsParams {
     pub cards: Vec<u64>,
 }
 
+/// An entry of the result of `cardsInfo` or `notesInfo`
+///
+/// AnkiConnect answers with an empty object for cards and notes that don't exist. Any
+/// other object has to decode as `T`, so malformed entries are reported instead of
+/// being taken for missing ones.
+#[derive(Debug)]
+pub(crate) enum InfoEntry<T> {
+    Found(T),
+    Missing,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for InfoEntry<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Object(map) if map.is_empty() => Ok(InfoEntry::Missing),
+            value => serde_json::from_value(value)
+                .map(InfoEntry::Found)
+                .map_err(D::Error::custom),
+        }
+    }
+}
+
+/// Card info as returned by `cardsInfo`
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CardInfoDto {
+    pub card_id: u64,
+    pub note: u64,
+    pub deck_name: String,
+    pub model_name: String,
+    pub ord: usize,
+    pub fields: HashMap<String, FieldInfo>,
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub answer: String,
+    #[serde(rename = "type")]
+    pub card_type: i8,
+    pub queue: i8,
+    pub due: i64,
+    pub interval: i64,
+    #[serde(default)]
+    pub factor: u32,
+    #[serde(default)]
+    pub reps: u32,
+    #[serde(default)]
+    pub lapses: u32,
+    #[serde(rename = "mod", default)]
+    pub modified: u64,
+}
+
+/// Parameters for getting the intervals of cards
+#[derive(Serialize, Debug)]
+pub(crate) struct GetIntervalsParams {
+    pub cards: Vec<u64>,
+    pub complete: bool,
+}
+
+/// Response for the complete interval history of a card
+///
+/// AnkiConnect answers with a plain `0` instead of a list for cards that were never
+/// reviewed.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum IntervalHistoryEntry {
+    History(Vec<i64>),
+    Unreviewed(i64),
+}
+
+/// Parameters for forgetting cards
+///
+/// The options are only sent if they differ from AnkiConnect's defaults, which older
+/// releases don't accept.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ForgetCardsParams {
+    pub cards: Vec<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restore_position: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_counts: Option<bool>,
+}
+
+/// Parameters for setting the due date of cards
+#[derive(Serialize, Debug)]
+pub(crate) struct SetDueDateParams {
+    pub cards: Vec<u64>,
+    pub days: String,
+}
+
+/// Parameters for answering cards
+#[derive(Serialize, Debug)]
+pub(crate) struct AnswerCardsParams {
+    pub answers: Vec<AnswerDto>,
+}
+
+/// The answer to a single card
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AnswerDto {
+    pub card_id: u64,
+    pub ease: u8,
+}
+
+/// Parameters for setting ease factors
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SetEaseFactorsParams {
+    pub cards: Vec<u64>,
+    pub ease_factors: Vec<u32>,
+}
+
 /// Parameters for setting a flag
 #[derive(Serialize, Debug)]
 pub(crate) struct SetFlagParams {
@@ -123,6 +240,14 @@ pub struct NoteInfo {
     pub fields: HashMap<String, FieldInfo>,
 }
 
+/// The tags of a note as returned by `notesInfo`
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NoteTagsDto {
+    pub note_id: u64,
+    pub tags: Vec<String>,
+}
+
 /// Field info in note info
 #[derive(Deserialize, Debug)]
 pub struct FieldInfo {
@@ -148,6 +273,7 @@ pub(crate) struct CreateDeckParams<'a> {
 }
 
 /// Parameters for renaming a deck
+#[allow(dead_code)]
 #[derive(Serialize, Debug)]
 pub(crate) struct RenameDeckParams<'a> {
     pub deck: &'a str,
@@ -197,6 +323,7 @@ pub struct DeckConfigDto {
 }
 
 /// Parameters for finding cards in a deck
+#[allow(dead_code)]
 #[derive(Serialize, Debug)]
 pub(crate) struct DeckCardParams<'a> {
     pub deck: &'a str,
@@ -261,6 +388,13 @@ pub(crate) struct FindModelsByIdParams<'a> {
     pub model_ids: &'a [u64],
 }
 
+/// Parameters for finding models by name
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FindModelsByNameParams<'a> {
+    pub model_names: &'a [&'a str],
+}
+
 /// Parameters for getting model templates
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -299,28 +433,54 @@ pub(crate) struct CardTemplate {
 }
 
 /// Model details from API response
+///
+/// Only the ID, name and the name and position of each field are required; the rest
+/// depends on the Anki version.
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelDetails {
     pub id: u64,
     pub name: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub type_: u64,
-    #[serde(rename = "mod")]
+    #[serde(rename = "mod", default)]
     pub mod_: u64,
+    #[serde(default)]
     pub usn: i64,
+    #[serde(default)]
     pub sortf: i64,
+    #[serde(default)]
     pub did: Option<i64>,
+    #[serde(default)]
     pub tmpls: Vec<Template>,
     pub flds: Vec<Field>,
+    #[serde(default)]
     pub css: String,
+    #[serde(default)]
     pub latex_pre: String,
+    #[serde(default)]
     pub latex_post: String,
+    #[serde(default)]
     pub latexsvg: bool,
+    #[serde(default)]
     pub req: Vec<Requirement>,
+    #[serde(default)]
     pub original_stock_kind: i64,
 }
 
+impl ModelDetails {
+    /// Converts the details into a model with its fields ordered by `ord`
+    pub(crate) fn into_model(mut self) -> Result<Model> {
+        self.flds.sort_by_key(|field| field.ord);
+        let fields = self
+            .flds
+            .into_iter()
+            .map(|field| ModelField::new(field.name, field.ord as usize))
+            .collect();
+        Model::new(self.id, self.name, fields)
+    }
+}
+
 /// Template in model details
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -329,11 +489,17 @@ pub struct Template {
     pub ord: i64,
     pub qfmt: String,
     pub afmt: String,
+    #[serde(default)]
     pub bqfmt: String,
+    #[serde(default)]
     pub bafmt: String,
+    #[serde(default)]
     pub did: Option<i64>,
+    #[serde(default)]
     pub bfont: String,
+    #[serde(default)]
     pub bsize: i64,
+    #[serde(default)]
     pub id: u64,
 }
 
@@ -343,16 +509,27 @@ pub struct Template {
 pub struct Field {
     pub name: String,
     pub ord: i64,
+    #[serde(default)]
     pub sticky: bool,
+    #[serde(default)]
     pub rtl: bool,
+    #[serde(default)]
     pub font: String,
+    #[serde(default)]
     pub size: i64,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub plain_text: bool,
+    #[serde(default)]
     pub collapsed: bool,
+    #[serde(default)]
     pub exclude_from_search: bool,
+    #[serde(default)]
     pub id: i64,
+    #[serde(default)]
     pub tag: Option<String>,
+    #[serde(default)]
     pub prevent_deletion: bool,
 }
 
@@ -433,3 +610,57 @@ pub(crate) struct DuplicateScopeOptionsDto {
     pub check_children: bool,
     pub check_all_models: bool,
 }
+
+// ------------------------
+// Permission-related params
+// ------------------------
+
+/// Response for requesting permission
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionDto {
+    pub permission: String,
+    #[serde(default)]
+    pub require_apikey: bool,
+    pub version: Option<u16>,
+}
+
+// ------------------------
+// Reflection-related params
+// ------------------------
+
+/// Parameters for listing the actions AnkiConnect supports
+#[derive(Serialize, Debug)]
+pub struct ApiReflectParams {
+    pub scopes: Vec<String>,
+    /// The actions to check, `None` for all of them
+    pub actions: Option<Vec<String>>,
+}
+
+/// Response for listing the actions AnkiConnect supports
+#[derive(Deserialize, Debug)]
+pub struct ApiReflectDto {
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub actions: Vec<String>,
+}
+
+// -------------------
+// Batch-related params
+// -------------------
+
+/// Parameters for running several actions in a single `multi` request
+#[derive(Serialize, Debug)]
+pub(crate) struct MultiParams<'a> {
+    pub actions: &'a [MultiAction],
+}
+
+/// A single action inside a `multi` request
+#[derive(Serialize, Debug)]
+pub(crate) struct MultiAction {
+    pub action: String,
+    pub version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
@@ -1,5 +1,8 @@
 //! Error types for the ankiconnect-rs crate
 
+use std::fmt;
+use std::time::Duration;
+
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, AnkiError>;
@@ -7,14 +10,66 @@ pub type Result<T> = std::result::Result<T, AnkiError>;
 /// Main error type for the ankiconnect-rs crate
 #[derive(Error, Debug)]
 pub enum AnkiError {
-    /// Error from the AnkiConnect API
-    #[error(transparent)]
-    AnkiConnectError(#[from] AnkiConnectError),
+    /// AnkiConnect reported an error for an action
+    #[error(
+        "Action '{action}' failed: {error}{}",
+        params.as_ref().map(|params| format!(" (params: {})", params)).unwrap_or_default()
+    )]
+    ActionFailed {
+        /// The name of the action
+        action: String,
+        /// A summary of the parameters, with API keys and media redacted
+        params: Option<String>,
+        /// The error message exactly as reported by AnkiConnect
+        message: String,
+        /// The recognized error
+        #[source]
+        error: AnkiConnectError,
+    },
 
     /// HTTP request error
     #[error("HTTP request failed: {0}")]
     HttpError(#[from] ureq::Error),
 
+    /// HTTP request error of the async client
+    #[cfg(feature = "async")]
+    #[error("HTTP request failed: {0}")]
+    AsyncHttpError(#[from] reqwest::Error),
+
+    /// The request failed on every attempt
+    #[error(
+        "Request '{action}' failed after {} attempts: {}",
+        attempts.len(),
+        attempts.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    RequestFailed {
+        action: String,
+        attempts: Vec<FailedAttempt>,
+    },
+
+    /// AnkiConnect did not become ready in time
+    #[error("AnkiConnect was not ready after {}ms: {last_error}", waited.as_millis())]
+    NotReady {
+        waited: Duration,
+        last_error: String,
+    },
+
+    /// The connected AnkiConnect does not offer an action
+    #[error(
+        "Action '{action}' is not supported by AnkiConnect version {version}, \
+         try updating the AnkiConnect add-on"
+    )]
+    ActionNotSupported {
+        /// The name of the action
+        action: String,
+        /// The version of the connected AnkiConnect
+        version: u16,
+    },
+
+    /// A cassette could not be read or written, or a request did not match it
+    #[error("Cassette error: {0}")]
+    CassetteError(String),
+
     /// JSON parsing error
     #[error("JSON parsing failed: {0}")]
     JsonError(String),
@@ -26,6 +81,10 @@ pub enum AnkiError {
         model_name: String,
     },
 
+    /// AnkiConnect denied this client permission to send requests
+    #[error("Permission denied by AnkiConnect (is the origin listed in `webCorsOriginList`?)")]
+    PermissionDenied,
+
     /// Validation error
     #[error("Validation error: {0}")]
     ValidationError(String),
@@ -34,6 +93,94 @@ pub enum AnkiError {
     UnknownError(String),
 }
 
+impl AnkiError {
+    /// Gets the error reported by AnkiConnect, if this is one
+    pub fn anki_connect_error(&self) -> Option<&AnkiConnectError> {
+        match self {
+            AnkiError::ActionFailed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the action that failed, if known
+    pub fn action(&self) -> Option<&str> {
+        match self {
+            AnkiError::ActionFailed { action, .. }
+            | AnkiError::ActionNotSupported { action, .. }
+            | AnkiError::RequestFailed { action, .. } => Some(action),
+            _ => None,
+        }
+    }
+
+    /// Checks if a deck, model, note, card or field was not found
+    pub fn is_not_found(&self) -> bool {
+        self.anki_connect_error()
+            .is_some_and(AnkiConnectError::is_not_found)
+    }
+
+    /// Checks if the error is about a duplicate note or model
+    pub fn is_duplicate(&self) -> bool {
+        self.anki_connect_error()
+            .is_some_and(AnkiConnectError::is_duplicate)
+    }
+
+    /// Checks if sending the request again later may succeed
+    ///
+    /// This is the case for transport failures and while the collection is unavailable
+    /// (e.g. during a sync). Note that AnkiConnect may already have performed an action
+    /// whose response was lost, so only resend actions that are safe to repeat.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AnkiError::HttpError(error) => {
+                matches!(
+                    error,
+                    ureq::Error::ConnectionFailed
+                        | ureq::Error::HostNotFound
+                        | ureq::Error::Timeout(_)
+                        | ureq::Error::Io(_)
+                        | ureq::Error::BodyStalled
+                ) || matches!(error, ureq::Error::StatusCode(status) if *status >= 500)
+            }
+            #[cfg(feature = "async")]
+            AnkiError::AsyncHttpError(error) => {
+                error.is_connect()
+                    || error.is_timeout()
+                    || error
+                        .status()
+                        .is_some_and(|status| status.is_server_error())
+            }
+            AnkiError::RequestFailed { .. } | AnkiError::NotReady { .. } => true,
+            _ => matches!(
+                self.anki_connect_error(),
+                Some(AnkiConnectError::CollectionUnavailable)
+            ),
+        }
+    }
+}
+
+/// A failed attempt to send a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedAttempt {
+    /// The number of the attempt, starting at 1
+    pub attempt: u32,
+    /// The time since the first attempt was started
+    pub elapsed: Duration,
+    /// Description of the error
+    pub error: String,
+}
+
+impl fmt::Display for FailedAttempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "attempt {} ({}ms): {}",
+            self.attempt,
+            self.elapsed.as_millis(),
+            self.error
+        )
+    }
+}
+
 /// Errors specific to the AnkiConnect API
 #[derive(Error, Debug)]
 pub enum AnkiConnectError {
@@ -69,6 +216,34 @@ pub enum AnkiConnectError {
     #[error("Model name already exists")]
     ModelNameExists,
 
+    /// AnkiConnect requires an API key and none or a wrong one was sent
+    #[error("A valid API key must be provided")]
+    InvalidApiKey,
+
+    /// The specified note was not found
+    #[error("Note not found: {0}")]
+    NoteNotFound(String),
+
+    /// The specified card was not found
+    #[error("Card not found: {0}")]
+    CardNotFound(String),
+
+    /// A field was not found in the model or note
+    #[error("Field not found: {0}")]
+    FieldNotFound(String),
+
+    /// A card template is missing or invalid
+    #[error("Template error: {0}")]
+    TemplateError(String),
+
+    /// A media file could not be found, read or stored
+    #[error("Media error: {0}")]
+    MediaError(String),
+
+    /// No collection is open, e.g. while Anki is syncing or on the profile manager
+    #[error("The collection is not available")]
+    CollectionUnavailable,
+
     /// Invalid column ID
     #[error("Invalid column ID: {0}")]
     InvalidColumnId(String),
@@ -82,6 +257,28 @@ pub enum AnkiConnectError {
     Other(String),
 }
 
+impl AnkiConnectError {
+    /// Checks if a deck, model, note, card or field was not found
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            AnkiConnectError::DeckNotFound(_)
+                | AnkiConnectError::ModelNotFound(_)
+                | AnkiConnectError::NoteNotFound(_)
+                | AnkiConnectError::CardNotFound(_)
+                | AnkiConnectError::FieldNotFound(_)
+        )
+    }
+
+    /// Checks if the error is about a duplicate note or model
+    pub fn is_duplicate(&self) -> bool {
+        matches!(
+            self,
+            AnkiConnectError::DuplicateNote | AnkiConnectError::ModelNameExists
+        )
+    }
+}
+
 /// Errors that can occur when creating or manipulating notes
 #[derive(Error, Debug)]
 pub enum NoteError {
@@ -130,7 +327,7 @@ impl From<AnkiConnectError> for NoteError {
             AnkiConnectError::EmptyQuestion => NoteError::EmptyQuestion,
             AnkiConnectError::DuplicateNote => NoteError::DuplicateNote,
             AnkiConnectError::EmptyNote => NoteError::ValidationError("Note is empty".to_string()),
-            e => NoteError::AnkiError(e.into()),
+            e => NoteError::ValidationError(e.to_string()),
         }
     }
 }
@@ -0,0 +1,1327 @@
+//! Implementations of the AnkiConnect actions supported by the fake backend
+//!
+//! Every action takes the collection and the (possibly empty) params object and
+//! returns the result value or the error message AnkiConnect would report.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::collection::{now_secs, Collection, FakeCard, FakeModel, FakeNote, FakeTemplate};
+use super::search::{find_cards, find_notes, glob_match};
+
+type ActionResult = Result<Value, String>;
+
+/// The actions the fake supports, as reported by `apiReflect`
+///
+/// Keep this in sync with [`dispatch`].
+pub(crate) const ACTIONS: &[&str] = &[
+    "addNote",
+    "addNotes",
+    "addTags",
+    "answerCards",
+    "apiReflect",
+    "areDue",
+    "areSuspended",
+    "canAddNotes",
+    "cardsInfo",
+    "cardsToNotes",
+    "changeDeck",
+    "createDeck",
+    "createModel",
+    "deckNames",
+    "deckNamesAndIds",
+    "deleteDecks",
+    "deleteMediaFile",
+    "deleteNotes",
+    "findCards",
+    "findModelsById",
+    "findModelsByName",
+    "findNotes",
+    "forgetCards",
+    "getActiveProfile",
+    "getDeckStats",
+    "getDecks",
+    "getEaseFactors",
+    "getIntervals",
+    "getMediaDirPath",
+    "getMediaFilesNames",
+    "getTags",
+    "guiBrowse",
+    "modelFieldNames",
+    "modelNames",
+    "modelNamesAndIds",
+    "modelStyling",
+    "modelTemplates",
+    "multi",
+    "notesInfo",
+    "relearnCards",
+    "removeTags",
+    "requestPermission",
+    "retrieveMediaFile",
+    "setDueDate",
+    "setEaseFactors",
+    "setFlag",
+    "storeMediaFile",
+    "suspend",
+    "suspended",
+    "unsuspend",
+    "updateModelStyling",
+    "updateNote",
+    "updateNoteFields",
+    "version",
+];
+
+/// Runs a single action against the collection
+pub(crate) fn dispatch(collection: &mut Collection, action: &str, params: &Value) -> ActionResult {
+    match action {
+        "version" => Ok(json!(crate::http::API_VERSION)),
+        "getActiveProfile" => Ok(json!("User 1")),
+        "apiReflect" => api_reflect(params),
+        "requestPermission" => Ok(json!({
+            "permission": "granted",
+            "requireApikey": false,
+            "version": crate::http::API_VERSION,
+        })),
+
+        // Decks
+        "deckNames" => Ok(json!(collection.decks.keys().collect::<Vec<_>>())),
+        "deckNamesAndIds" => Ok(json!(collection.decks)),
+        "getDecks" => get_decks(collection, params),
+        "createDeck" => {
+            let deck: String = param(params, "deck")?;
+            Ok(json!(collection.ensure_deck(&deck)))
+        }
+        "changeDeck" => change_deck(collection, params),
+        "deleteDecks" => delete_decks(collection, params),
+        "getDeckStats" => get_deck_stats(collection, params),
+
+        // Cards
+        "findCards" | "guiBrowse" => {
+            let query: String = param(params, "query")?;
+            Ok(json!(find_cards(collection, &query)?))
+        }
+        "cardsInfo" => cards_info(collection, params),
+        "cardsToNotes" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let notes: BTreeSet<u64> = cards
+                .iter()
+                .filter_map(|id| collection.cards.get(id))
+                .map(|card| card.note_id)
+                .collect();
+            Ok(json!(notes))
+        }
+        "suspend" => set_suspended(collection, params, true),
+        "unsuspend" => set_suspended(collection, params, false),
+        "suspended" => {
+            let card: u64 = param(params, "card")?;
+            let card = collection
+                .cards
+                .get(&card)
+                .ok_or_else(|| format!("Card was not found: {}", card))?;
+            Ok(json!(card.queue == -1))
+        }
+        "areSuspended" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            Ok(json!(cards
+                .iter()
+                .map(|id| collection.cards.get(id).map(|card| card.queue == -1))
+                .collect::<Vec<_>>()))
+        }
+        "areDue" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let due = cards
+                .iter()
+                .map(|id| {
+                    collection
+                        .cards
+                        .get(id)
+                        .map(|card| card.is_due(collection.today))
+                        .ok_or_else(|| format!("Card was not found: {}", id))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(json!(due))
+        }
+        "getIntervals" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let complete: bool = optional_param(params, "complete")?.unwrap_or(false);
+            let intervals = cards
+                .iter()
+                .map(|id| {
+                    let card = collection
+                        .cards
+                        .get(id)
+                        .ok_or_else(|| format!("Card was not found: {}", id))?;
+                    // The fake keeps no review log, so the history is the current interval.
+                    // Like AnkiConnect, it answers with a plain 0 for cards never reviewed.
+                    Ok(match (complete, card.reps) {
+                        (false, _) => json!(card.interval),
+                        (true, 0) => json!(0),
+                        (true, _) => json!([card.interval]),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(json!(intervals))
+        }
+        "answerCards" => answer_cards(collection, params),
+        "forgetCards" => forget_cards(collection, params),
+        "relearnCards" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            for id in cards {
+                if let Some(card) = collection.cards.get_mut(&id) {
+                    if card.card_type == 2 {
+                        card.card_type = 3;
+                        card.queue = 1;
+                        card.due = now_secs() as i64;
+                        card.modified = now_secs();
+                    }
+                }
+            }
+            Ok(Value::Null)
+        }
+        "setDueDate" => set_due_date(collection, params),
+        "getEaseFactors" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let factors = cards
+                .iter()
+                .map(|id| collection.cards.get(id).map(|card| card.factor))
+                .collect::<Vec<_>>();
+            Ok(json!(factors))
+        }
+        "setEaseFactors" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let factors: Vec<u32> = param(params, "easeFactors")?;
+            Ok(json!(cards
+                .iter()
+                .zip(factors)
+                .map(|(id, factor)| match collection.cards.get_mut(id) {
+                    Some(card) => {
+                        card.factor = factor;
+                        card.modified = now_secs();
+                        true
+                    }
+                    None => false,
+                })
+                .collect::<Vec<_>>()))
+        }
+        "setFlag" => {
+            let cards: Vec<u64> = param(params, "cards")?;
+            let flag: u8 = param(params, "flag")?;
+            for id in cards {
+                if let Some(card) = collection.cards.get_mut(&id) {
+                    card.flags = flag;
+                    card.modified = now_secs();
+                }
+            }
+            Ok(json!(true))
+        }
+
+        // Notes
+        "findNotes" => {
+            let query: String = param(params, "query")?;
+            Ok(json!(find_notes(collection, &query)?))
+        }
+        "notesInfo" => notes_info(collection, params),
+        "addNote" => {
+            let note: NewNote = param(params, "note")?;
+            add_note(collection, note).map(|id| json!(id))
+        }
+        "addNotes" => {
+            let notes: Vec<NewNote> = param(params, "notes")?;
+            Ok(json!(notes
+                .into_iter()
+                .map(|note| add_note(collection, note).ok())
+                .collect::<Vec<_>>()))
+        }
+        "canAddNotes" => {
+            let notes: Vec<NewNote> = param(params, "notes")?;
+            Ok(json!(notes
+                .iter()
+                .map(|note| check_note(collection, note).is_ok())
+                .collect::<Vec<_>>()))
+        }
+        "updateNoteFields" | "updateNote" => update_note(collection, params),
+        "deleteNotes" => {
+            let notes: Vec<u64> = param(params, "notes")?;
+            collection.remove_notes(&notes);
+            Ok(Value::Null)
+        }
+        "addTags" => change_tags(collection, params, true),
+        "removeTags" => change_tags(collection, params, false),
+        "getTags" => {
+            let tags: BTreeSet<&String> = collection
+                .notes
+                .values()
+                .flat_map(|note| &note.tags)
+                .collect();
+            Ok(json!(tags))
+        }
+
+        // Models
+        "modelNames" => Ok(json!(collection.models.keys().collect::<Vec<_>>())),
+        "modelNamesAndIds" => Ok(json!(collection
+            .models
+            .values()
+            .map(|model| (&model.name, model.id))
+            .collect::<BTreeMap<_, _>>())),
+        "modelFieldNames" => {
+            let model = model_param(collection, params)?;
+            Ok(json!(model.fields))
+        }
+        "modelTemplates" => {
+            let model = model_param(collection, params)?;
+            let templates: Map<String, Value> = model
+                .templates
+                .iter()
+                .map(|t| (t.name.clone(), json!({"Front": t.front, "Back": t.back})))
+                .collect();
+            Ok(Value::Object(templates))
+        }
+        "modelStyling" => {
+            let model = model_param(collection, params)?;
+            Ok(json!({ "css": model.css }))
+        }
+        "updateModelStyling" => {
+            let update: ModelStylingUpdate = param(params, "model")?;
+            let model = collection
+                .models
+                .get_mut(&update.name)
+                .ok_or_else(|| format!("model was not found: {}", update.name))?;
+            model.css = update.css;
+            Ok(Value::Null)
+        }
+        "createModel" => create_model(collection, params),
+        "findModelsById" => {
+            let ids: Vec<u64> = param(params, "modelIds")?;
+            ids.iter()
+                .map(|id| {
+                    collection
+                        .model_by_id(*id)
+                        .map(model_details)
+                        .ok_or_else(|| format!("model was not found: {}", id))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        }
+        "findModelsByName" => {
+            let names: Vec<String> = param(params, "modelNames")?;
+            names
+                .iter()
+                .map(|name| {
+                    collection
+                        .models
+                        .get(name)
+                        .map(model_details)
+                        .ok_or_else(|| format!("model was not found: {}", name))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        }
+
+        // Media
+        "storeMediaFile" => {
+            let file: MediaFile = serde_json::from_value(params.clone())
+                .map_err(|e| format!("invalid params: {}", e))?;
+            let delete_existing = file.delete_existing;
+            store_media(collection, file, delete_existing).map(|name| json!(name))
+        }
+        "retrieveMediaFile" => {
+            let filename: String = param(params, "filename")?;
+            Ok(collection
+                .media
+                .get(&filename)
+                .map_or(json!(false), |data| json!(data)))
+        }
+        "getMediaFilesNames" => {
+            let pattern: String = optional_param(params, "pattern")?.unwrap_or("*".to_string());
+            Ok(json!(collection
+                .media
+                .keys()
+                .filter(|name| glob_match(&pattern, name))
+                .collect::<Vec<_>>()))
+        }
+        "getMediaDirPath" => Ok(json!(std::env::temp_dir()
+            .join("fake-anki")
+            .join("collection.media"))),
+        "deleteMediaFile" => {
+            let filename: String = param(params, "filename")?;
+            collection.media.remove(&filename);
+            Ok(Value::Null)
+        }
+
+        _ => Err("unsupported action".to_string()),
+    }
+}
+
+/// Reads a required parameter
+fn param<T: DeserializeOwned>(params: &Value, name: &str) -> Result<T, String> {
+    optional_param(params, name)?.ok_or_else(|| format!("missing required parameter: '{}'", name))
+}
+
+/// Reads an optional parameter, treating `null` as missing
+fn optional_param<T: DeserializeOwned>(params: &Value, name: &str) -> Result<Option<T>, String> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| format!("invalid parameter '{}': {}", name, e)),
+    }
+}
+
+/// Reads the `modelName` parameter and looks up the model
+fn model_param<'a>(collection: &'a Collection, params: &Value) -> Result<&'a FakeModel, String> {
+    let name: String = param(params, "modelName")?;
+    collection
+        .models
+        .get(&name)
+        .ok_or_else(|| format!("model was not found: {}", name))
+}
+
+fn cards_info(collection: &Collection, params: &Value) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+
+    Ok(Value::Array(
+        cards
+            .iter()
+            .map(|id| {
+                let Some(card) = collection.cards.get(id) else {
+                    return json!({});
+                };
+                let Some(note) = collection.notes.get(&card.note_id) else {
+                    return json!({});
+                };
+                let model = collection.models.get(&note.model);
+                let template = model.and_then(|model| model.templates.get(card.ord as usize));
+                let question = template
+                    .map(|template| render(&template.front, &note.fields, ""))
+                    .unwrap_or_default();
+                let answer = template
+                    .map(|template| render(&template.back, &note.fields, &question))
+                    .unwrap_or_default();
+
+                json!({
+                    "cardId": card.id,
+                    "note": card.note_id,
+                    "deckName": collection.deck_name(card.deck_id).unwrap_or_default(),
+                    "modelName": note.model,
+                    "ord": card.ord,
+                    "fieldOrder": card.ord,
+                    "fields": field_infos(collection, note),
+                    "question": question,
+                    "answer": answer,
+                    "css": model.map(|model| model.css.as_str()).unwrap_or_default(),
+                    "type": card.card_type,
+                    "queue": card.queue,
+                    "due": card.due,
+                    "interval": card.interval,
+                    "factor": card.factor,
+                    "reps": card.reps,
+                    "lapses": card.lapses,
+                    "left": card.left,
+                    "mod": card.modified,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Builds the `fields` object of `cardsInfo` and `notesInfo`
+///
+/// Fields the model doesn't know about are ordered after the model's own, so `order`
+/// is always a number like in AnkiConnect.
+fn field_infos(collection: &Collection, note: &FakeNote) -> Map<String, Value> {
+    let order = collection
+        .models
+        .get(&note.model)
+        .map(|model| model.fields.as_slice())
+        .unwrap_or_default();
+    let mut unknown = order.len();
+
+    note.fields
+        .iter()
+        .map(|(name, value)| {
+            let position = order
+                .iter()
+                .position(|field| field == name)
+                .unwrap_or_else(|| {
+                    unknown += 1;
+                    unknown - 1
+                });
+            (name.clone(), json!({"value": value, "order": position}))
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Answer {
+    card_id: u64,
+    ease: u8,
+}
+
+/// Answers cards with a much simplified version of Anki's scheduler
+fn answer_cards(collection: &mut Collection, params: &Value) -> ActionResult {
+    let answers: Vec<Answer> = param(params, "answers")?;
+    // Anki rejects the whole request before answering any card
+    if let Some(answer) = answers
+        .iter()
+        .find(|answer| !(1..=4).contains(&answer.ease))
+    {
+        return Err(format!("invalid ease: {}", answer.ease));
+    }
+
+    let today = collection.today;
+    let results: Vec<bool> = answers
+        .into_iter()
+        .map(|answer| {
+            let Some(card) = collection.cards.get_mut(&answer.card_id) else {
+                return false;
+            };
+            if card.factor == 0 {
+                card.factor = 2500;
+            }
+            card.reps += 1;
+            match answer.ease {
+                1 => {
+                    if card.card_type == 2 {
+                        card.lapses += 1;
+                        card.card_type = 3;
+                    } else if card.card_type == 0 {
+                        card.card_type = 1;
+                    }
+                    card.queue = 1;
+                    card.due = now_secs() as i64 + 60;
+                }
+                ease => {
+                    let multiplier = match ease {
+                        2 => 1.2,
+                        3 => card.factor as f64 / 1000.0,
+                        _ => card.factor as f64 / 1000.0 * 1.3,
+                    };
+                    card.interval = ((card.interval.max(1) as f64) * multiplier).round() as i64;
+                    card.card_type = 2;
+                    card.queue = 2;
+                    card.due = today + card.interval;
+                }
+            }
+            card.modified = now_secs();
+            true
+        })
+        .collect();
+    Ok(json!(results))
+}
+
+fn forget_cards(collection: &mut Collection, params: &Value) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+    let restore_position: bool = optional_param(params, "restorePosition")?.unwrap_or(true);
+    let reset_counts: bool = optional_param(params, "resetCounts")?.unwrap_or(false);
+
+    for id in cards {
+        let position = collection.new_position();
+        if let Some(card) = collection.cards.get_mut(&id) {
+            card.card_type = 0;
+            card.queue = 0;
+            card.due = card
+                .original_position
+                .filter(|_| restore_position)
+                .unwrap_or(position);
+            card.interval = 0;
+            card.factor = 0;
+            if reset_counts {
+                card.reps = 0;
+                card.lapses = 0;
+            }
+            card.modified = now_secs();
+        }
+    }
+    Ok(Value::Null)
+}
+
+fn set_due_date(collection: &mut Collection, params: &Value) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+    let days: String = param(params, "days")?;
+
+    // The fake always picks the start of a range
+    let (range, set_interval) = match days.strip_suffix('!') {
+        Some(range) => (range, true),
+        None => (days.as_str(), false),
+    };
+    let delay: i64 = range
+        .split('-')
+        .next()
+        .and_then(|start| start.trim().parse().ok())
+        .ok_or_else(|| format!("invalid days: {}", days))?;
+
+    let today = collection.today;
+    for id in cards {
+        if let Some(card) = collection.cards.get_mut(&id) {
+            if card.card_type == 0 || set_interval {
+                card.interval = delay.max(1);
+            }
+            card.card_type = 2;
+            card.queue = 2;
+            card.due = today + delay;
+            card.modified = now_secs();
+        }
+    }
+    Ok(json!(true))
+}
+
+/// Renders a card template by substituting plain field references
+///
+/// Filters, conditionals and cloze deletions are not supported.
+fn render(template: &str, fields: &BTreeMap<String, String>, front_side: &str) -> String {
+    let mut rendered = template.replace("{{FrontSide}}", front_side);
+    for (name, value) in fields {
+        rendered = rendered.replace(&format!("{{{{{}}}}}", name), value);
+    }
+    rendered
+}
+
+// -----
+// Decks
+// -----
+
+fn get_decks(collection: &Collection, params: &Value) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+
+    let mut decks: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for id in cards {
+        if let Some(card) = collection.cards.get(&id) {
+            let deck = collection.deck_name(card.deck_id).unwrap_or_default();
+            decks.entry(deck).or_default().push(id);
+        }
+    }
+    Ok(json!(decks))
+}
+
+fn change_deck(collection: &mut Collection, params: &Value) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+    let deck: String = param(params, "deck")?;
+
+    let deck_id = collection.ensure_deck(&deck);
+    for id in cards {
+        if let Some(card) = collection.cards.get_mut(&id) {
+            card.deck_id = deck_id;
+            card.modified = now_secs();
+        }
+    }
+    Ok(Value::Null)
+}
+
+fn delete_decks(collection: &mut Collection, params: &Value) -> ActionResult {
+    let decks: Vec<String> = param(params, "decks")?;
+    let cards_too: bool = optional_param(params, "cardsToo")?.unwrap_or(false);
+    if !cards_too {
+        return Err(
+            "Since Anki 2.1.28 it's not possible to delete decks without deleting cards as well"
+                .to_string(),
+        );
+    }
+
+    let is_deleted = |name: &str| {
+        decks
+            .iter()
+            .any(|deck| name == deck || name.starts_with(&format!("{}::", deck)))
+    };
+    let deleted_ids: Vec<u64> = collection
+        .decks
+        .iter()
+        .filter(|(name, _)| is_deleted(name))
+        .map(|(_, id)| *id)
+        .collect();
+
+    let note_ids: BTreeSet<u64> = collection
+        .cards
+        .values()
+        .filter(|card| deleted_ids.contains(&card.deck_id))
+        .map(|card| card.note_id)
+        .collect();
+    collection
+        .cards
+        .retain(|_, card| !deleted_ids.contains(&card.deck_id));
+    // Notes without any remaining cards are removed as well
+    for note_id in note_ids {
+        if collection.cards_of_note(note_id).is_empty() {
+            collection.notes.remove(&note_id);
+        }
+    }
+
+    // The default deck can be emptied but never deleted
+    collection
+        .decks
+        .retain(|name, id| *id == 1 || !is_deleted(name));
+    Ok(Value::Null)
+}
+
+fn get_deck_stats(collection: &Collection, params: &Value) -> ActionResult {
+    let decks: Vec<String> = param(params, "decks")?;
+
+    let mut stats = Map::new();
+    for name in decks {
+        let Some(deck_id) = collection.decks.get(&name) else {
+            continue;
+        };
+        let cards: Vec<&FakeCard> = collection
+            .cards
+            .values()
+            .filter(|card| {
+                collection
+                    .deck_name(card.deck_id)
+                    .is_some_and(|deck| deck == name || deck.starts_with(&format!("{}::", name)))
+            })
+            .collect();
+        let count = |predicate: &dyn Fn(&FakeCard) -> bool| {
+            cards.iter().filter(|card| predicate(card)).count()
+        };
+
+        stats.insert(
+            deck_id.to_string(),
+            json!({
+                "deck_id": deck_id,
+                "name": name,
+                "new_count": count(&|card| card.queue == 0),
+                "learn_count": count(&|card| matches!(card.queue, 1 | 3)),
+                "review_count": count(&|card| card.queue == 2 && card.due <= collection.today),
+                "total_in_deck": cards.len(),
+            }),
+        );
+    }
+    Ok(Value::Object(stats))
+}
+
+// -----
+// Cards
+// -----
+
+fn set_suspended(collection: &mut Collection, params: &Value, suspend: bool) -> ActionResult {
+    let cards: Vec<u64> = param(params, "cards")?;
+
+    let mut changed = false;
+    for id in cards {
+        let Some(card) = collection.cards.get_mut(&id) else {
+            continue;
+        };
+        if (card.queue == -1) == suspend {
+            continue;
+        }
+        card.queue = if suspend {
+            -1
+        } else {
+            match card.card_type {
+                // Relearning cards go back into the learning queue
+                3 => 1,
+                card_type => card_type as i8,
+            }
+        };
+        card.modified = now_secs();
+        changed = true;
+    }
+    Ok(json!(changed))
+}
+
+// -----
+// Notes
+// -----
+
+/// A note as passed to `addNote`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewNote {
+    deck_name: String,
+    model_name: String,
+    fields: BTreeMap<String, String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    options: NewNoteOptions,
+    #[serde(default)]
+    audio: Vec<MediaFile>,
+    #[serde(default)]
+    video: Vec<MediaFile>,
+    #[serde(default)]
+    picture: Vec<MediaFile>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct NewNoteOptions {
+    #[serde(default)]
+    allow_duplicate: bool,
+    duplicate_scope: Option<String>,
+    duplicate_scope_options: Option<DuplicateScopeOptions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DuplicateScopeOptions {
+    deck_name: Option<String>,
+    #[serde(default)]
+    check_children: bool,
+    #[serde(default)]
+    check_all_models: bool,
+}
+
+/// A media file as passed to `storeMediaFile` or attached to a note
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MediaFile {
+    filename: String,
+    data: Option<String>,
+    path: Option<PathBuf>,
+    url: Option<String>,
+    #[serde(default)]
+    delete_existing: bool,
+    #[serde(default)]
+    fields: Vec<String>,
+}
+
+/// Checks whether a note can be added, returning its model and the template ordinals
+/// of the cards it would generate
+fn check_note<'a>(
+    collection: &'a Collection,
+    note: &NewNote,
+) -> Result<(&'a FakeModel, Vec<u32>), String> {
+    let model = collection
+        .models
+        .get(&note.model_name)
+        .ok_or_else(|| format!("model was not found: {}", note.model_name))?;
+    if !collection.decks.contains_key(&note.deck_name) {
+        return Err(format!("deck was not found: {}", note.deck_name));
+    }
+
+    let fields = note_fields(model, &note.fields);
+    let first_field = &fields[&model.fields[0]];
+    if first_field.trim().is_empty() {
+        return Err("cannot create note because it is empty".to_string());
+    }
+
+    let ords = card_ordinals(model, &fields);
+    if ords.is_empty() {
+        return Err(
+            "The field values you have provided would make an empty question on all cards."
+                .to_string(),
+        );
+    }
+
+    if !note.options.allow_duplicate && is_duplicate(collection, model, note, first_field) {
+        return Err("cannot create note because it is a duplicate".to_string());
+    }
+
+    Ok((model, ords))
+}
+
+fn is_duplicate(
+    collection: &Collection,
+    model: &FakeModel,
+    note: &NewNote,
+    first_field: &str,
+) -> bool {
+    let options = note.options.duplicate_scope_options.as_ref();
+    let check_all_models = options.is_some_and(|o| o.check_all_models);
+    let deck_scope = if note.options.duplicate_scope.as_deref() == Some("deck") {
+        let deck = options
+            .and_then(|o| o.deck_name.clone())
+            .unwrap_or_else(|| note.deck_name.clone());
+        Some((deck, options.is_some_and(|o| o.check_children)))
+    } else {
+        None
+    };
+
+    collection.notes.values().any(|existing| {
+        if !check_all_models && existing.model != model.name {
+            return false;
+        }
+        let Some(existing_model) = collection.models.get(&existing.model) else {
+            return false;
+        };
+        let existing_first = existing
+            .fields
+            .get(&existing_model.fields[0])
+            .map(String::as_str)
+            .unwrap_or_default();
+        if existing_first.trim() != first_field.trim() {
+            return false;
+        }
+
+        match &deck_scope {
+            None => true,
+            Some((deck, check_children)) => {
+                collection.cards_of_note(existing.id).iter().any(|id| {
+                    let name = collection
+                        .deck_name(collection.cards[id].deck_id)
+                        .unwrap_or_default();
+                    name == deck || (*check_children && name.starts_with(&format!("{}::", deck)))
+                })
+            }
+        }
+    })
+}
+
+/// Builds the complete field map of a note, matching names case-insensitively and
+/// ignoring unknown fields like AnkiConnect does
+fn note_fields(model: &FakeModel, values: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    model
+        .fields
+        .iter()
+        .map(|field| {
+            let value = values
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field))
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default();
+            (field.clone(), value)
+        })
+        .collect()
+}
+
+/// Determines which cards a note generates
+fn card_ordinals(model: &FakeModel, fields: &BTreeMap<String, String>) -> Vec<u32> {
+    if model.is_cloze {
+        let mut numbers = BTreeSet::new();
+        for value in fields.values() {
+            for (i, _) in value.match_indices("{{c") {
+                let digits: String = value[i + 3..]
+                    .chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect();
+                if value[i + 3 + digits.len()..].starts_with("::") {
+                    if let Ok(number) = digits.parse::<u32>() {
+                        if number > 0 {
+                            numbers.insert(number - 1);
+                        }
+                    }
+                }
+            }
+        }
+        return numbers.into_iter().collect();
+    }
+
+    model
+        .templates
+        .iter()
+        .enumerate()
+        .filter(|(_, template)| {
+            let references = field_references(&template.front);
+            references.is_empty()
+                || references.iter().any(|name| {
+                    fields
+                        .get(name)
+                        .is_some_and(|value| !value.trim().is_empty())
+                })
+        })
+        .map(|(ord, _)| ord as u32)
+        .collect()
+}
+
+/// Extracts the names of the fields referenced by a template
+fn field_references(template: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let reference = rest[start + 2..start + end].trim();
+        let reference = reference.trim_start_matches(['#', '^', '/']);
+        // Strip filters like `text:` or `cloze:`
+        let name = reference.rsplit(':').next().unwrap_or_default().trim();
+        if !name.is_empty() && name != "FrontSide" {
+            references.push(name.to_string());
+        }
+        rest = &rest[start + end + 2..];
+    }
+    references
+}
+
+fn add_note(collection: &mut Collection, note: NewNote) -> Result<u64, String> {
+    let (model, ords) = check_note(collection, &note)?;
+    let mut fields = note_fields(model, &note.fields);
+    let model_name = model.name.clone();
+
+    for (file, tag) in note
+        .audio
+        .into_iter()
+        .map(|file| (file, "sound"))
+        .chain(note.video.into_iter().map(|file| (file, "sound")))
+        .chain(note.picture.into_iter().map(|file| (file, "img")))
+    {
+        let targets = file.fields.clone();
+        let filename = store_media(collection, file, false)?;
+        let reference = if tag == "img" {
+            format!("<img src=\"{}\">", filename)
+        } else {
+            format!("[sound:{}]", filename)
+        };
+        for target in targets {
+            if let Some(value) = fields.get_mut(&target) {
+                value.push_str(&reference);
+            }
+        }
+    }
+
+    let note_id = collection.next_id();
+    let deck_id = collection.decks[&note.deck_name];
+    for ord in ords {
+        let card_id = collection.next_id();
+        let position = collection.new_position();
+        collection.cards.insert(
+            card_id,
+            FakeCard::new(card_id, note_id, deck_id, ord, position),
+        );
+    }
+    collection.notes.insert(
+        note_id,
+        FakeNote {
+            id: note_id,
+            model: model_name,
+            fields,
+            tags: note.tags.into_iter().collect(),
+            modified: now_secs(),
+        },
+    );
+
+    Ok(note_id)
+}
+
+fn notes_info(collection: &Collection, params: &Value) -> ActionResult {
+    let ids = match optional_param::<String>(params, "query")? {
+        Some(query) => find_notes(collection, &query)?,
+        None => param(params, "notes")?,
+    };
+
+    Ok(Value::Array(
+        ids.iter()
+            .map(|id| {
+                let Some(note) = collection.notes.get(id) else {
+                    return json!({});
+                };
+                json!({
+                    "noteId": note.id,
+                    "modelName": note.model,
+                    "tags": note.tags,
+                    "fields": field_infos(collection, note),
+                    "mod": note.modified,
+                    "cards": collection.cards_of_note(note.id),
+                })
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize)]
+struct NoteUpdate {
+    id: u64,
+    fields: Option<BTreeMap<String, String>>,
+    tags: Option<Vec<String>>,
+}
+
+fn update_note(collection: &mut Collection, params: &Value) -> ActionResult {
+    let update: NoteUpdate = param(params, "note")?;
+    let note = collection
+        .notes
+        .get_mut(&update.id)
+        .ok_or_else(|| format!("note was not found: {}", update.id))?;
+
+    if let Some(fields) = update.fields {
+        for (name, value) in fields {
+            if let Some((_, content)) = note
+                .fields
+                .iter_mut()
+                .find(|(field, _)| field.eq_ignore_ascii_case(&name))
+            {
+                *content = value;
+            }
+        }
+    }
+    if let Some(tags) = update.tags {
+        note.tags = tags.into_iter().collect();
+    }
+    note.modified = now_secs();
+    Ok(Value::Null)
+}
+
+fn change_tags(collection: &mut Collection, params: &Value, add: bool) -> ActionResult {
+    let notes: Vec<u64> = param(params, "notes")?;
+    let tags: String = param(params, "tags")?;
+
+    for id in notes {
+        let Some(note) = collection.notes.get_mut(&id) else {
+            continue;
+        };
+        for tag in tags.split_whitespace() {
+            if add {
+                note.tags.insert(tag.to_string());
+            } else {
+                note.tags
+                    .retain(|existing| !existing.eq_ignore_ascii_case(tag));
+            }
+        }
+        note.modified = now_secs();
+    }
+    Ok(Value::Null)
+}
+
+// ------
+// Models
+// ------
+
+#[derive(Deserialize)]
+struct ModelStylingUpdate {
+    name: String,
+    css: String,
+}
+
+/// A card template as passed to `createModel`
+#[derive(Deserialize)]
+struct NewTemplate {
+    #[serde(rename = "Name", alias = "name", default)]
+    name: Option<String>,
+    #[serde(rename = "Front", alias = "front")]
+    front: String,
+    #[serde(rename = "Back", alias = "back")]
+    back: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NewTemplates {
+    List(Vec<NewTemplate>),
+    Map(BTreeMap<String, NewTemplate>),
+}
+
+fn create_model(collection: &mut Collection, params: &Value) -> ActionResult {
+    let name: String = param(params, "modelName")?;
+    let fields: Vec<String> = param(params, "inOrderFields")?;
+    let css: Option<String> = optional_param(params, "css")?;
+    let is_cloze: bool = optional_param(params, "isCloze")?.unwrap_or(false);
+    let templates: NewTemplates = param(params, "cardTemplates")?;
+
+    if collection.models.contains_key(&name) {
+        return Err("Model name already exists".to_string());
+    }
+
+    let templates: Vec<FakeTemplate> = match templates {
+        NewTemplates::List(templates) => templates
+            .into_iter()
+            .enumerate()
+            .map(|(i, template)| FakeTemplate {
+                name: template.name.unwrap_or_else(|| format!("Card {}", i + 1)),
+                front: template.front,
+                back: template.back,
+            })
+            .collect(),
+        NewTemplates::Map(templates) => templates
+            .into_iter()
+            .map(|(name, template)| FakeTemplate {
+                name,
+                front: template.front,
+                back: template.back,
+            })
+            .collect(),
+    };
+
+    let model = FakeModel {
+        id: collection.next_id(),
+        name,
+        fields,
+        templates,
+        css: css.unwrap_or_default(),
+        is_cloze,
+    };
+    let details = model_details(&model);
+    collection.insert_model(model);
+    Ok(details)
+}
+
+/// Builds the model dictionary returned by `createModel` and `findModelsBy*`
+fn model_details(model: &FakeModel) -> Value {
+    let templates: Vec<Value> = model
+        .templates
+        .iter()
+        .enumerate()
+        .map(|(ord, template)| {
+            json!({
+                "name": template.name,
+                "ord": ord,
+                "qfmt": template.front,
+                "afmt": template.back,
+                "bqfmt": "",
+                "bafmt": "",
+                "did": null,
+                "bfont": "",
+                "bsize": 0,
+                "id": model.id + 1 + ord as u64,
+            })
+        })
+        .collect();
+    let fields: Vec<Value> = model
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(ord, name)| {
+            json!({
+                "name": name,
+                "ord": ord,
+                "sticky": false,
+                "rtl": false,
+                "font": "Arial",
+                "size": 20,
+                "description": "",
+                "plainText": false,
+                "collapsed": false,
+                "excludeFromSearch": false,
+                "id": ord,
+                "tag": null,
+                "preventDeletion": false,
+            })
+        })
+        .collect();
+    let requirements: Vec<Value> = (0..model.templates.len())
+        .map(|ord| json!([ord, "any", [0]]))
+        .collect();
+
+    json!({
+        "id": model.id,
+        "name": model.name,
+        "type": if model.is_cloze { 1 } else { 0 },
+        "mod": 0,
+        "usn": -1,
+        "sortf": 0,
+        "did": null,
+        "tmpls": templates,
+        "flds": fields,
+        "css": model.css,
+        "latexPre": "\\documentclass[12pt]{article}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+        "latexsvg": false,
+        "req": requirements,
+        "originalStockKind": 0,
+    })
+}
+
+// -----
+// Media
+// -----
+
+/// Stores a media file, returning the filename that was used
+///
+/// Files from a URL are not downloaded; they are stored with empty content.
+fn store_media(
+    collection: &mut Collection,
+    file: MediaFile,
+    delete_existing: bool,
+) -> Result<String, String> {
+    let data = if let Some(data) = file.data {
+        data
+    } else if let Some(path) = file.path {
+        let bytes = std::fs::read(&path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        base64_encode(&bytes)
+    } else if file.url.is_some() {
+        String::new()
+    } else {
+        return Err("You must provide a \"data\", \"path\", or \"url\" field.".to_string());
+    };
+
+    let mut filename = file.filename;
+    if !delete_existing {
+        let (stem, extension) = match filename.rsplit_once('.') {
+            Some((stem, extension)) => (stem.to_string(), format!(".{}", extension)),
+            None => (filename.clone(), String::new()),
+        };
+        let mut counter = 1;
+        while collection
+            .media
+            .get(&filename)
+            .is_some_and(|existing| *existing != data)
+        {
+            filename = format!("{}-{}{}", stem, counter, extension);
+            counter += 1;
+        }
+    }
+
+    collection.media.insert(filename.clone(), data);
+    Ok(filename)
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn api_reflect(params: &Value) -> ActionResult {
+    let scopes: Vec<String> = param(params, "scopes")?;
+    let filter: Option<Vec<String>> = optional_param(params, "actions")?;
+
+    let mut result = Map::new();
+    result.insert("scopes".to_string(), json!(scopes));
+    if scopes.iter().any(|scope| scope == "actions") {
+        let actions: Vec<&str> = ACTIONS
+            .iter()
+            .copied()
+            .filter(|action| {
+                filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.iter().any(|name| name == action))
+            })
+            .collect();
+        result.insert("actions".to_string(), json!(actions));
+    }
+    Ok(Value::Object(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflected_actions_are_dispatched() {
+        for action in ACTIONS.iter().filter(|action| **action != "multi") {
+            let result = dispatch(&mut Collection::new(), action, &json!({}));
+            assert_ne!(
+                result.err().as_deref(),
+                Some("unsupported action"),
+                "{}",
+                action
+            );
+        }
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_field_references() {
+        assert_eq!(
+            field_references("{{Front}} {{#Back}}{{text:Back}}{{/Back}} {{FrontSide}}"),
+            vec!["Front", "Back", "Back", "Back"]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! In-memory state of a fake Anki collection
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// The first ID handed out for newly created objects
+const FIRST_ID: u64 = 1_700_000_000_000;
+
+/// The complete state of a fake Anki collection
+///
+/// A collection can be serialized to and deserialized from JSON, which makes it
+/// usable as a test fixture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    /// The next ID handed out for a deck, model, note or card
+    pub next_id: u64,
+    /// The current day, relative to the creation of the collection
+    #[serde(default)]
+    pub today: i64,
+    /// Deck name -> deck ID
+    pub decks: BTreeMap<String, u64>,
+    /// Model name -> model
+    pub models: BTreeMap<String, FakeModel>,
+    /// Note ID -> note
+    pub notes: BTreeMap<u64, FakeNote>,
+    /// Card ID -> card
+    pub cards: BTreeMap<u64, FakeCard>,
+    /// Filename -> base64-encoded content
+    pub media: BTreeMap<String, String>,
+}
+
+/// A model (note type) in the fake collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FakeModel {
+    pub id: u64,
+    pub name: String,
+    pub fields: Vec<String>,
+    pub templates: Vec<FakeTemplate>,
+    pub css: String,
+    #[serde(default)]
+    pub is_cloze: bool,
+}
+
+/// A card template of a fake model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FakeTemplate {
+    pub name: String,
+    pub front: String,
+    pub back: String,
+}
+
+/// A note in the fake collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FakeNote {
+    pub id: u64,
+    pub model: String,
+    pub fields: BTreeMap<String, String>,
+    pub tags: BTreeSet<String>,
+    pub modified: u64,
+}
+
+/// A card in the fake collection
+///
+/// The scheduling fields follow Anki's own representation: `queue` is -3/-2 for
+/// buried, -1 for suspended, 0 for new, 1/3 for learning and 2 for review cards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FakeCard {
+    pub id: u64,
+    pub note_id: u64,
+    pub deck_id: u64,
+    pub ord: u32,
+    #[serde(rename = "type")]
+    pub card_type: u8,
+    pub queue: i8,
+    pub due: i64,
+    /// The position in the new queue the card was added at, restored by `forgetCards`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_position: Option<i64>,
+    pub interval: i64,
+    pub factor: u32,
+    pub reps: u32,
+    pub lapses: u32,
+    pub left: u32,
+    pub flags: u8,
+    pub modified: u64,
+}
+
+impl Collection {
+    /// Creates a collection with the "Default" deck and the stock note types
+    /// "Basic", "Basic (and reversed card)" and "Cloze"
+    pub fn new() -> Self {
+        let mut collection = Self::empty();
+        collection.decks.insert("Default".to_string(), 1);
+
+        let stock_css = ".card {\n    font-family: arial;\n    font-size: 20px;\n    text-align: center;\n    color: black;\n    background-color: white;\n}\n";
+        collection.insert_model(FakeModel {
+            id: 1_600_000_000_001,
+            name: "Basic".to_string(),
+            fields: vec!["Front".to_string(), "Back".to_string()],
+            templates: vec![FakeTemplate {
+                name: "Card 1".to_string(),
+                front: "{{Front}}".to_string(),
+                back: "{{FrontSide}}\n\n<hr id=answer>\n\n{{Back}}".to_string(),
+            }],
+            css: stock_css.to_string(),
+            is_cloze: false,
+        });
+        collection.insert_model(FakeModel {
+            id: 1_600_000_000_002,
+            name: "Basic (and reversed card)".to_string(),
+            fields: vec!["Front".to_string(), "Back".to_string()],
+            templates: vec![
+                FakeTemplate {
+                    name: "Card 1".to_string(),
+                    front: "{{Front}}".to_string(),
+                    back: "{{FrontSide}}\n\n<hr id=answer>\n\n{{Back}}".to_string(),
+                },
+                FakeTemplate {
+                    name: "Card 2".to_string(),
+                    front: "{{Back}}".to_string(),
+                    back: "{{FrontSide}}\n\n<hr id=answer>\n\n{{Front}}".to_string(),
+                },
+            ],
+            css: stock_css.to_string(),
+            is_cloze: false,
+        });
+        collection.insert_model(FakeModel {
+            id: 1_600_000_000_003,
+            name: "Cloze".to_string(),
+            fields: vec!["Text".to_string(), "Back Extra".to_string()],
+            templates: vec![FakeTemplate {
+                name: "Cloze".to_string(),
+                front: "{{cloze:Text}}".to_string(),
+                back: "{{cloze:Text}}<br>\n{{Back Extra}}".to_string(),
+            }],
+            css: stock_css.to_string(),
+            is_cloze: true,
+        });
+
+        collection
+    }
+
+    /// Creates a collection without any decks, models, notes or media
+    pub fn empty() -> Self {
+        Self {
+            next_id: FIRST_ID,
+            today: 0,
+            decks: BTreeMap::new(),
+            models: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            cards: BTreeMap::new(),
+            media: BTreeMap::new(),
+        }
+    }
+
+    /// Hands out a new unique ID
+    pub(crate) fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Adds a model, replacing any model with the same name
+    pub fn insert_model(&mut self, model: FakeModel) {
+        self.models.insert(model.name.clone(), model);
+    }
+
+    /// Gets the ID of a deck, creating it (and its parents) if necessary
+    pub(crate) fn ensure_deck(&mut self, name: &str) -> u64 {
+        if let Some((parent, _)) = name.rsplit_once("::") {
+            self.ensure_deck(parent);
+        }
+
+        if let Some(id) = self.decks.get(name) {
+            return *id;
+        }
+
+        let id = self.next_id();
+        self.decks.insert(name.to_string(), id);
+        id
+    }
+
+    /// Gets the name of the deck with the given ID
+    pub(crate) fn deck_name(&self, id: u64) -> Option<&str> {
+        self.decks
+            .iter()
+            .find(|(_, deck_id)| **deck_id == id)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Gets the model with the given ID
+    pub(crate) fn model_by_id(&self, id: u64) -> Option<&FakeModel> {
+        self.models.values().find(|model| model.id == id)
+    }
+
+    /// Gets the IDs of all cards of a note, ordered by template
+    pub(crate) fn cards_of_note(&self, note_id: u64) -> Vec<u64> {
+        let mut cards: Vec<&FakeCard> = self
+            .cards
+            .values()
+            .filter(|card| card.note_id == note_id)
+            .collect();
+        cards.sort_by_key(|card| card.ord);
+        cards.into_iter().map(|card| card.id).collect()
+    }
+
+    /// Removes notes together with their cards
+    pub(crate) fn remove_notes(&mut self, note_ids: &[u64]) {
+        for note_id in note_ids {
+            self.notes.remove(note_id);
+        }
+        self.cards
+            .retain(|_, card| !note_ids.contains(&card.note_id));
+    }
+
+    /// Gets the position after the last card in the new queue
+    pub(crate) fn new_position(&self) -> i64 {
+        self.cards
+            .values()
+            .filter_map(|card| {
+                let due = (card.card_type == 0).then_some(card.due);
+                due.max(card.original_position)
+            })
+            .max()
+            .map_or(0, |last| last + 1)
+    }
+}
+
+impl Default for Collection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeCard {
+    /// Creates a new, unstudied card
+    pub(crate) fn new(id: u64, note_id: u64, deck_id: u64, ord: u32, position: i64) -> Self {
+        Self {
+            id,
+            note_id,
+            deck_id,
+            ord,
+            card_type: 0,
+            queue: 0,
+            due: position,
+            original_position: Some(position),
+            interval: 0,
+            factor: 0,
+            reps: 0,
+            lapses: 0,
+            left: 0,
+            flags: 0,
+            modified: now_secs(),
+        }
+    }
+
+    /// Checks if the card is due for review or in learning, as `is:due` does
+    pub(crate) fn is_due(&self, today: i64) -> bool {
+        self.queue == 1 || (matches!(self.queue, 2 | 3) && self.due <= today)
+    }
+}
+
+/// Gets the current time in seconds since the Unix epoch
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,146 @@
+//! An in-memory fake of AnkiConnect for tests
+//!
+//! [`FakeAnki`] implements [`RequestSender`] on top of an in-memory [`Collection`], so
+//! code built on [`AnkiClient`](crate::AnkiClient) can be tested without a running Anki.
+//! It speaks the AnkiConnect protocol (version 6), including `multi`, and reports
+//! errors with the same messages as AnkiConnect.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use ankiconnect_rs::fake::FakeAnki;
+//! use ankiconnect_rs::AnkiClient;
+//!
+//! # fn main() -> ankiconnect_rs::Result<()> {
+//! let fake = Arc::new(FakeAnki::new());
+//! let client = AnkiClient::with_sender(fake.clone());
+//!
+//! client.decks().create("Japanese::Vocab")?;
+//!
+//! assert!(fake.collection().decks.contains_key("Japanese::Vocab"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only the subset of actions used by this crate is supported; other actions fail
+//! with "unsupported action".
+
+mod actions;
+mod collection;
+mod search;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+pub use collection::{Collection, FakeCard, FakeModel, FakeNote, FakeTemplate};
+
+use crate::error::Result;
+use crate::http::{AnkiConnectResponse, RequestSender};
+
+/// In-memory fake AnkiConnect backend
+pub struct FakeAnki {
+    collection: Mutex<Collection>,
+}
+
+impl FakeAnki {
+    /// Creates a fake with the "Default" deck and the stock note types
+    pub fn new() -> Self {
+        Self::with_collection(Collection::new())
+    }
+
+    /// Creates a fake backed by the given collection, e.g. one loaded from a fixture
+    pub fn with_collection(collection: Collection) -> Self {
+        Self {
+            collection: Mutex::new(collection),
+        }
+    }
+
+    /// Gets a snapshot of the current state of the collection
+    pub fn collection(&self) -> Collection {
+        self.lock().clone()
+    }
+
+    /// Replaces the whole collection, e.g. with one loaded from a fixture
+    pub fn replace_collection(&self, collection: Collection) {
+        *self.lock() = collection;
+    }
+
+    /// Handles a single AnkiConnect request
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to perform
+    /// * `params` - The parameters for the action
+    ///
+    /// # Returns
+    ///
+    /// The response envelope AnkiConnect would send
+    pub fn handle(&self, action: &str, params: Option<Value>) -> AnkiConnectResponse {
+        let params = params.unwrap_or_else(|| Value::Object(Default::default()));
+        handle_action(&mut self.lock(), action, &params)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Collection> {
+        // The collection stays consistent even if a test panicked while holding the lock
+        self.collection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for FakeAnki {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestSender for FakeAnki {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        Ok(self.handle(action, params))
+    }
+}
+
+/// An action inside a `multi` request
+#[derive(Deserialize)]
+struct MultiAction {
+    action: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn handle_action(collection: &mut Collection, action: &str, params: &Value) -> AnkiConnectResponse {
+    if action == "multi" {
+        let actions: Vec<MultiAction> = match params
+            .get("actions")
+            .map(|actions| serde_json::from_value(actions.clone()))
+        {
+            Some(Ok(actions)) => actions,
+            Some(Err(e)) => return AnkiConnectResponse::error(format!("invalid actions: {}", e)),
+            None => return AnkiConnectResponse::error("missing required parameter: 'actions'"),
+        };
+
+        let responses: Vec<AnkiConnectResponse> = actions
+            .into_iter()
+            .map(|inner| {
+                let params = match inner.params {
+                    Value::Null => Value::Object(Default::default()),
+                    params => params,
+                };
+                handle_action(collection, &inner.action, &params)
+            })
+            .collect();
+
+        return match serde_json::to_value(responses) {
+            Ok(result) => AnkiConnectResponse::success(result),
+            Err(e) => AnkiConnectResponse::error(e.to_string()),
+        };
+    }
+
+    match actions::dispatch(collection, action, params) {
+        Ok(result) => AnkiConnectResponse::success(result),
+        Err(error) => AnkiConnectResponse::error(error),
+    }
+}
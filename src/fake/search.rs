@@ -0,0 +1,458 @@
+//! A small evaluator for Anki's search syntax
+//!
+//! Supports the subset of the syntax produced by [`QueryBuilder`](crate::QueryBuilder):
+//! free text, `field:value`, `deck:`, `note:`, `tag:`, `is:`, `flag:`, `nid:`, `cid:`,
+//! `mid:`, `card:` and `prop:` terms, negation with `-`, `or` and parentheses.
+
+use super::collection::{Collection, FakeCard, FakeModel, FakeNote};
+
+/// Finds the IDs of all cards matching the query
+pub(crate) fn find_cards(collection: &Collection, query: &str) -> Result<Vec<u64>, String> {
+    let expr = parse(query)?;
+
+    let mut ids = Vec::new();
+    for card in collection.cards.values() {
+        let Some(note) = collection.notes.get(&card.note_id) else {
+            continue;
+        };
+        let Some(model) = collection.models.get(&note.model) else {
+            continue;
+        };
+
+        let context = Context {
+            collection,
+            card,
+            note,
+            model,
+        };
+        if expr.matches(&context)? {
+            ids.push(card.id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Finds the IDs of all notes with at least one card matching the query
+pub(crate) fn find_notes(collection: &Collection, query: &str) -> Result<Vec<u64>, String> {
+    let mut ids = Vec::new();
+    for card_id in find_cards(collection, query)? {
+        let note_id = collection.cards[&card_id].note_id;
+        if !ids.contains(&note_id) {
+            ids.push(note_id);
+        }
+    }
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+/// Everything a search term can look at
+struct Context<'a> {
+    collection: &'a Collection,
+    card: &'a FakeCard,
+    note: &'a FakeNote,
+    model: &'a FakeModel,
+}
+
+enum Expr {
+    All,
+    Term(String),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+impl Expr {
+    fn matches(&self, context: &Context<'_>) -> Result<bool, String> {
+        match self {
+            Self::All => Ok(true),
+            Self::Term(term) => matches_term(term, context),
+            Self::Not(expr) => Ok(!expr.matches(context)?),
+            Self::And(exprs) => {
+                for expr in exprs {
+                    if !expr.matches(context)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Self::Or(exprs) => {
+                for expr in exprs {
+                    if expr.matches(context)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Not,
+    Or,
+    Term(String),
+}
+
+/// Splits a query into tokens, removing quotes but keeping backslash escapes
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::Open);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::Close);
+        } else if c == '-' {
+            chars.next();
+            tokens.push(Token::Not);
+        } else {
+            let mut term = String::new();
+            let mut in_quotes = false;
+            while let Some(&c) = chars.peek() {
+                if !in_quotes && (c.is_whitespace() || c == '(' || c == ')') {
+                    break;
+                }
+                chars.next();
+                match c {
+                    '"' => in_quotes = !in_quotes,
+                    '\\' => {
+                        term.push('\\');
+                        if let Some(escaped) = chars.next() {
+                            term.push(escaped);
+                        }
+                    }
+                    c => term.push(c),
+                }
+            }
+            if in_quotes {
+                return Err(format!("Invalid search: unbalanced quotes in '{}'", query));
+            }
+
+            if term.eq_ignore_ascii_case("or") {
+                tokens.push(Token::Or);
+            } else if term.eq_ignore_ascii_case("and") {
+                // AND is implicit
+            } else if !term.is_empty() {
+                tokens.push(Token::Term(term));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse(query: &str) -> Result<Expr, String> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Ok(Expr::All);
+    }
+
+    let mut position = 0;
+    let expr = parse_or(&tokens, &mut position)?;
+    if position != tokens.len() {
+        return Err(format!("Invalid search: unexpected ')' in '{}'", query));
+    }
+    Ok(expr)
+}
+
+fn parse_or(tokens: &[Token], position: &mut usize) -> Result<Expr, String> {
+    let mut alternatives = vec![parse_and(tokens, position)?];
+    while tokens.get(*position) == Some(&Token::Or) {
+        *position += 1;
+        alternatives.push(parse_and(tokens, position)?);
+    }
+
+    Ok(if alternatives.len() == 1 {
+        alternatives.remove(0)
+    } else {
+        Expr::Or(alternatives)
+    })
+}
+
+fn parse_and(tokens: &[Token], position: &mut usize) -> Result<Expr, String> {
+    let mut terms = Vec::new();
+    while let Some(token) = tokens.get(*position) {
+        if matches!(token, Token::Or | Token::Close) {
+            break;
+        }
+        terms.push(parse_unary(tokens, position)?);
+    }
+
+    match terms.len() {
+        0 => Err("Invalid search: expected a search term".to_string()),
+        1 => Ok(terms.remove(0)),
+        _ => Ok(Expr::And(terms)),
+    }
+}
+
+fn parse_unary(tokens: &[Token], position: &mut usize) -> Result<Expr, String> {
+    let token = tokens
+        .get(*position)
+        .ok_or_else(|| "Invalid search: expected a search term".to_string())?;
+    *position += 1;
+
+    match token {
+        Token::Not => Ok(Expr::Not(Box::new(parse_unary(tokens, position)?))),
+        Token::Open => {
+            let expr = parse_or(tokens, position)?;
+            if tokens.get(*position) != Some(&Token::Close) {
+                return Err("Invalid search: missing ')'".to_string());
+            }
+            *position += 1;
+            Ok(expr)
+        }
+        Token::Term(term) => Ok(Expr::Term(term.clone())),
+        Token::Or | Token::Close => Err("Invalid search: expected a search term".to_string()),
+    }
+}
+
+/// Splits a term at its first unescaped colon
+fn split_term(term: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, c) in term.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ':' => return Some((&term[..i], &term[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn matches_term(term: &str, context: &Context<'_>) -> Result<bool, String> {
+    let Some((key, value)) = split_term(term) else {
+        // Free text: search all fields
+        let pattern = format!("*{}*", term);
+        return Ok(context
+            .note
+            .fields
+            .values()
+            .any(|content| glob_match(&pattern, content)));
+    };
+
+    let card = context.card;
+    match key.to_lowercase().as_str() {
+        "deck" => {
+            if value == "*" {
+                return Ok(true);
+            }
+            let value = if value == "current" { "Default" } else { value };
+            let deck = context
+                .collection
+                .deck_name(card.deck_id)
+                .unwrap_or_default();
+            Ok(glob_match(value, deck) || glob_match(&format!("{}::*", value), deck))
+        }
+        "note" => Ok(glob_match(value, &context.model.name)),
+        "tag" => {
+            if value == "none" {
+                return Ok(context.note.tags.is_empty());
+            }
+            Ok(context
+                .note
+                .tags
+                .iter()
+                .any(|tag| glob_match(value, tag) || glob_match(&format!("{}::*", value), tag)))
+        }
+        "is" => match value {
+            "new" => Ok(card.card_type == 0),
+            "learn" => Ok(matches!(card.queue, 1 | 3)),
+            "review" => Ok(matches!(card.card_type, 2 | 3)),
+            "due" => Ok(card.is_due(context.collection.today)),
+            "suspended" => Ok(card.queue == -1),
+            "buried" => Ok(matches!(card.queue, -2 | -3)),
+            "buried-sibling" => Ok(card.queue == -2),
+            "buried-manually" => Ok(card.queue == -3),
+            _ => Err(format!("Invalid search: unknown state 'is:{}'", value)),
+        },
+        "flag" => {
+            let flag: u8 = parse_number(term, value)?;
+            Ok(card.flags & 0b111 == flag)
+        }
+        "nid" => id_list_contains(term, value, context.note.id),
+        "cid" => id_list_contains(term, value, card.id),
+        "mid" => id_list_contains(term, value, context.model.id),
+        "card" => match value.parse::<u32>() {
+            Ok(number) => Ok(card.ord + 1 == number),
+            Err(_) => Ok(context
+                .model
+                .templates
+                .get(card.ord as usize)
+                .is_some_and(|template| glob_match(value, &template.name))),
+        },
+        "prop" => matches_property(term, value, context),
+        "added" | "rated" | "edited" | "introduced" | "resched" => Err(format!(
+            "Invalid search: '{}' is not supported by the fake backend",
+            term
+        )),
+        _ => {
+            // Field search: the whole field content has to match
+            let field = unescape(key);
+            Ok(context
+                .note
+                .fields
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&field))
+                .is_some_and(|(_, content)| glob_match(value, content)))
+        }
+    }
+}
+
+fn matches_property(term: &str, value: &str, context: &Context<'_>) -> Result<bool, String> {
+    let split = value
+        .find(['<', '>', '=', '!'])
+        .ok_or_else(|| format!("Invalid search: invalid property '{}'", term))?;
+    let (property, rest) = value.split_at(split);
+    let operator_len = if rest.starts_with("<=") || rest.starts_with(">=") || rest.starts_with("!=")
+    {
+        2
+    } else {
+        1
+    };
+    let (operator, number) = rest.split_at(operator_len);
+    let number: f64 = parse_number(term, number)?;
+
+    let card = context.card;
+    let actual = match property {
+        "ivl" => card.interval as f64,
+        "due" => (card.due - context.collection.today) as f64,
+        "reps" => card.reps as f64,
+        "lapses" => card.lapses as f64,
+        "ease" => card.factor as f64 / 1000.0,
+        _ => return Err(format!("Invalid search: unknown property '{}'", property)),
+    };
+
+    Ok(match operator {
+        "<" => actual < number,
+        "<=" => actual <= number,
+        ">" => actual > number,
+        ">=" => actual >= number,
+        "=" => actual == number,
+        "!=" => actual != number,
+        _ => return Err(format!("Invalid search: invalid property '{}'", term)),
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(term: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid search: invalid number in '{}'", term))
+}
+
+fn id_list_contains(term: &str, value: &str, id: u64) -> Result<bool, String> {
+    for part in value.split(',') {
+        if parse_number::<u64>(term, part)? == id {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Removes backslash escapes
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(escaped);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[derive(Clone, Copy)]
+enum Glob {
+    Literal(char),
+    AnyChar,
+    AnySequence,
+}
+
+/// Matches text against an Anki wildcard pattern, ignoring case
+///
+/// `*` matches any sequence, `_` any single character, and `\` escapes the next character.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut globs = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        globs.push(match c {
+            '*' => Glob::AnySequence,
+            '_' => Glob::AnyChar,
+            '\\' => match chars.next() {
+                Some(escaped) => Glob::Literal(escaped),
+                None => Glob::Literal('\\'),
+            },
+            c => Glob::Literal(c),
+        });
+    }
+    let text: Vec<char> = text.chars().collect();
+
+    // matched[j] is true if the globs processed so far match the first j characters
+    let mut matched = vec![false; text.len() + 1];
+    matched[0] = true;
+    for glob in globs {
+        let mut next = vec![false; text.len() + 1];
+        for j in 0..=text.len() {
+            next[j] = match glob {
+                Glob::AnySequence => matched[j] || (j > 0 && next[j - 1]),
+                Glob::AnyChar => j > 0 && matched[j - 1],
+                Glob::Literal(c) => {
+                    j > 0 && matched[j - 1] && chars_equal_ignore_case(c, text[j - 1])
+                }
+            };
+        }
+        matched = next;
+    }
+    matched[text.len()]
+}
+
+fn chars_equal_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("dog", "Dog"));
+        assert!(glob_match("d*g", "doing"));
+        assert!(glob_match("d_g", "dig"));
+        assert!(!glob_match("d_g", "doing"));
+        assert!(glob_match("Japanese\\:\\:Vocab", "Japanese::Vocab"));
+        assert!(glob_match("a\\_b", "a_b"));
+        assert!(!glob_match("a\\_b", "axb"));
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("deck:\"My Deck\" -tag:x or (a b)").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Term("deck:My Deck".to_string()),
+                Token::Not,
+                Token::Term("tag:x".to_string()),
+                Token::Or,
+                Token::Open,
+                Token::Term("a".to_string()),
+                Token::Term("b".to_string()),
+                Token::Close,
+            ]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Tracing instrumentation of the requests sent to AnkiConnect
+
+use std::time::Duration;
+
+use serde_json::Value;
+use tracing::field::Empty;
+use tracing::Span;
+
+use super::{parse_anki_connect_error, AnkiConnectResponse};
+use crate::error::AnkiError;
+use crate::redact;
+
+/// Creates the span for a request to AnkiConnect
+///
+/// # Arguments
+///
+/// * `action` - The action that is requested
+/// * `params` - The parameters of the action, which are logged with media redacted
+/// * `request_bytes` - The size of the serialized request
+pub(super) fn request_span(action: &str, params: Option<&Value>, request_bytes: usize) -> Span {
+    let span = tracing::debug_span!(
+        "ankiconnect",
+        action,
+        params = Empty,
+        request_bytes,
+        response_bytes = Empty,
+        duration_ms = Empty,
+        error = Empty,
+    );
+
+    // Only pay for the summary if someone is listening
+    if let (false, Some(params)) = (span.is_disabled(), params) {
+        span.record("params", redact::summarize_params(params).as_str());
+    }
+    span
+}
+
+/// Records the outcome of a request on its span and emits an event for it
+///
+/// # Arguments
+///
+/// * `span` - The span created by [`request_span`]
+/// * `duration` - How long the request took, including retries
+/// * `result` - The response and the size of its body, or the error
+pub(super) fn record_outcome(
+    span: &Span,
+    duration: Duration,
+    result: &Result<(AnkiConnectResponse, usize), AnkiError>,
+) {
+    span.record("duration_ms", duration.as_millis() as u64);
+
+    match result {
+        Ok((response, response_bytes)) => {
+            span.record("response_bytes", *response_bytes as u64);
+            match &response.error {
+                Some(message) => {
+                    span.record("error", "anki_connect");
+                    tracing::debug!(
+                        parent: span,
+                        error = %parse_anki_connect_error(message),
+                        message = message.as_str(),
+                        "AnkiConnect reported an error"
+                    );
+                }
+                None => tracing::debug!(parent: span, "AnkiConnect request succeeded"),
+            }
+        }
+        Err(error) => {
+            span.record("error", error_class(error));
+            tracing::warn!(parent: span, %error, "AnkiConnect request failed");
+        }
+    }
+}
+
+/// Classifies a failed request for filtering and metrics
+fn error_class(error: &AnkiError) -> &'static str {
+    match error {
+        AnkiError::HttpError(ureq::Error::Timeout(_)) => "timeout",
+        AnkiError::HttpError(ureq::Error::StatusCode(_)) => "http_status",
+        AnkiError::HttpError(_) => "transport",
+        #[cfg(feature = "async")]
+        AnkiError::AsyncHttpError(error) if error.is_timeout() => "timeout",
+        #[cfg(feature = "async")]
+        AnkiError::AsyncHttpError(error) if error.is_status() => "http_status",
+        #[cfg(feature = "async")]
+        AnkiError::AsyncHttpError(_) => "transport",
+        AnkiError::RequestFailed { .. } => "retries_exhausted",
+        AnkiError::JsonError(_) => "invalid_response",
+        _ => "other",
+    }
+}
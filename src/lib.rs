@@ -10,6 +10,9 @@
 //! - 🗃️ **Deck Operations**: Create decks, list existing decks  
 //! - 📦 **Media Handling**: Store media files from paths/URLs/base64 data  
 //! - 🧩 **Model Support**: Fetch field names, validate note structures  
+//! - 📨 **Batching**: Send many actions in a single `multi` request  
+//! - ⚡ **Async Support**: An async client behind the `async` feature  
+//! - 🧪 **Fake Backend**: An in-memory AnkiConnect fake behind the `fake` feature  
 //! - 🔄 **Error Handling**: Comprehensive error types for AnkiConnect-specific issues  
 //! - ✅ **Tested**: Mock server integration tests for all major operations
 //!
@@ -56,18 +59,31 @@
 
 // Re-export key types for a clean public API
 pub use builders::{NoteBuilder, QueryBuilder};
-pub use client::{AnkiClient, DuplicateScope};
-pub use error::{AnkiConnectError, AnkiError, NoteError, Result};
+pub use client::action::Action;
+#[cfg(feature = "async")]
+pub use client::asynchronous::AsyncAnkiClient;
+pub use client::{
+    AnkiClient, AnkiClientBuilder, Batch, BatchHandle, BatchResults, DuplicateScope, ForgetOptions,
+};
+pub use error::{AnkiConnectError, AnkiError, FailedAttempt, NoteError, Result};
+#[cfg(feature = "async")]
+pub use http::AsyncRequestSender;
+pub use http::{AnkiConnectResponse, RequestSender};
 pub use models::{
-    Card, CardId, Deck, DeckId, Field, FieldMedia, Media, MediaSource, MediaType, Model, Note,
-    NoteId,
+    Capabilities, Card, CardId, CardQueue, CardType, Deck, DeckId, DueDateSpec, Ease, EaseFactor,
+    Field, FieldMedia, Health, Interval, Media, MediaSource, MediaType, Model, Note, NoteId,
+    Permission,
 };
 
 // Public modules
 pub mod builders;
+pub mod cache;
+pub mod cassette;
 pub mod client;
 pub mod error;
+#[cfg(feature = "fake")]
+pub mod fake;
+pub mod http;
+pub mod limit;
 pub mod models;
-
-// Private modules
-mod http;
+mod redact;
@@ -0,0 +1,195 @@
+//! Client-side rate limiting of requests to AnkiConnect
+//!
+//! AnkiConnect handles requests one at a time on Anki's main thread, so many threads
+//! sending at once make requests queue up until they time out. [`RateLimitedSender`]
+//! wraps another [`RequestSender`] and bounds how many requests are in flight and how
+//! many are started per second. GUI actions (`gui*`) go first: they are not throttled
+//! and take the next free slot before any waiting bulk request.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use ankiconnect_rs::http::HttpRequestSender;
+//! use ankiconnect_rs::limit::{RateLimit, RateLimitedSender};
+//! use ankiconnect_rs::AnkiClient;
+//!
+//! # fn main() -> ankiconnect_rs::Result<()> {
+//! let limit = RateLimit {
+//!     max_in_flight: Some(2),
+//!     requests_per_second: Some(20.0),
+//! };
+//! let http = Arc::new(HttpRequestSender::new("localhost", 8765));
+//! let client = AnkiClient::with_sender(Arc::new(RateLimitedSender::new(http, limit)?));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::error::{AnkiError, Result};
+use crate::http::{AnkiConnectResponse, RequestSender};
+
+/// Limits for the requests sent to AnkiConnect
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimit {
+    /// The maximum number of requests waiting for a response at once, `None` for no
+    /// limit
+    pub max_in_flight: Option<usize>,
+    /// The maximum number of requests started per second, `None` for no limit
+    pub requests_per_second: Option<f64>,
+}
+
+/// The lane a request waits in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// Actions that open or drive Anki's GUI, usually triggered by a user
+    Gui,
+    /// All other actions, like bulk reads and writes
+    Bulk,
+}
+
+impl Lane {
+    /// Gets the lane of an action
+    pub fn of(action: &str) -> Self {
+        if action.starts_with("gui") {
+            Lane::Gui
+        } else {
+            Lane::Bulk
+        }
+    }
+}
+
+/// Bookkeeping shared by all threads sending through the limiter
+struct State {
+    in_flight: usize,
+    waiting_gui: usize,
+    next_start: Instant,
+}
+
+/// Decorator that bounds the concurrency and rate of requests
+pub struct RateLimitedSender {
+    inner: Arc<dyn RequestSender>,
+    max_in_flight: Option<usize>,
+    interval: Option<Duration>,
+    state: Mutex<State>,
+    released: Condvar,
+}
+
+impl RateLimitedSender {
+    /// Creates a limiter in front of the given sender
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The sender that actually sends the requests
+    /// * `limit` - The limits to enforce
+    ///
+    /// # Returns
+    ///
+    /// The limiter, or a validation error if a limit is zero or negative
+    pub fn new(inner: Arc<dyn RequestSender>, limit: RateLimit) -> Result<Self> {
+        if limit.max_in_flight == Some(0) {
+            return Err(AnkiError::ValidationError(
+                "max_in_flight must be at least 1".to_string(),
+            ));
+        }
+        let interval = match limit.requests_per_second {
+            Some(rate) if rate > 0.0 => {
+                Some(Duration::try_from_secs_f64(1.0 / rate).map_err(|e| {
+                    AnkiError::ValidationError(format!("Invalid requests_per_second: {}", e))
+                })?)
+            }
+            Some(rate) => {
+                return Err(AnkiError::ValidationError(format!(
+                    "requests_per_second must be positive, got {}",
+                    rate
+                )))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            inner,
+            max_in_flight: limit.max_in_flight,
+            interval,
+            state: Mutex::new(State {
+                in_flight: 0,
+                waiting_gui: 0,
+                next_start: Instant::now(),
+            }),
+            released: Condvar::new(),
+        })
+    }
+
+    /// Waits until a request of the given lane may be sent
+    ///
+    /// Bulk requests wait for their start time without holding a slot, so a GUI
+    /// request arriving meanwhile isn't queued behind the rate limit.
+    fn acquire(&self, lane: Lane) -> Permit<'_> {
+        let mut state = self.lock();
+        if lane == Lane::Gui {
+            state.waiting_gui += 1;
+        }
+        loop {
+            let full = self.max_in_flight.is_some_and(|max| state.in_flight >= max);
+            let yield_to_gui = lane == Lane::Bulk && state.waiting_gui > 0;
+            let now = Instant::now();
+            // GUI requests are not throttled
+            let early = lane == Lane::Bulk && self.interval.is_some() && state.next_start > now;
+            if !full && !yield_to_gui && !early {
+                break;
+            }
+            state = if full || yield_to_gui {
+                self.released
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner)
+            } else {
+                let delay = state.next_start - now;
+                self.released
+                    .wait_timeout(state, delay)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            };
+        }
+        if lane == Lane::Gui {
+            state.waiting_gui -= 1;
+            // Bulk requests that only yielded to GUI requests may take a free slot now
+            if state.waiting_gui == 0 {
+                self.released.notify_all();
+            }
+        }
+        state.in_flight += 1;
+
+        // Move the next start time, so waiting bulk requests are spread out evenly
+        if let Some(interval) = self.interval {
+            state.next_start = state.next_start.max(Instant::now()) + interval;
+        }
+        Permit { limiter: self }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RequestSender for RateLimitedSender {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        let _permit = self.acquire(Lane::of(action));
+        self.inner.send_raw(action, params)
+    }
+}
+
+/// A slot for a request in flight, released when dropped
+struct Permit<'a> {
+    limiter: &'a RateLimitedSender,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.limiter.lock().in_flight -= 1;
+        self.limiter.released.notify_all();
+    }
+}
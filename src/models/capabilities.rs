@@ -0,0 +1,44 @@
+//! Capability model definitions
+
+use std::collections::BTreeSet;
+
+/// What the connected AnkiConnect instance supports, as returned by
+/// [`AnkiClient::capabilities`](crate::AnkiClient::capabilities)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    version: u16,
+    actions: Option<BTreeSet<String>>,
+}
+
+impl Capabilities {
+    /// Creates the capabilities of an instance
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the AnkiConnect plugin
+    /// * `actions` - The supported actions, `None` if the instance can't list them
+    pub fn new(version: u16, actions: Option<BTreeSet<String>>) -> Self {
+        Self { version, actions }
+    }
+
+    /// Gets the version of the AnkiConnect plugin
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Gets the supported actions, if the instance could list them
+    ///
+    /// Releases of AnkiConnect without `apiReflect` can't, in which case this is `None`.
+    pub fn actions(&self) -> Option<&BTreeSet<String>> {
+        self.actions.as_ref()
+    }
+
+    /// Checks if the instance supports the given action
+    ///
+    /// If the supported actions are unknown, every action is assumed to be supported.
+    pub fn supports(&self, action: &str) -> bool {
+        self.actions
+            .as_ref()
+            .is_none_or(|actions| actions.contains(action))
+    }
+}
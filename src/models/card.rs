@@ -1,6 +1,8 @@
 //! Card model definitions
 
-use crate::models::{Field, MediaSource, MediaType, Model};
+use crate::client::request::CardInfoDto;
+use crate::error::{AnkiError, Result};
+use crate::models::{Field, MediaSource, MediaType, Model, NoteId};
 use std::collections::{HashMap, HashSet};
 
 /// Unique identifier for an Anki card
@@ -14,36 +16,205 @@ impl CardId {
     }
 }
 
-/// Represents a complete Anki card with all its data
+/// The ease factor of a card in permille, e.g. 2500 for 250%
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EaseFactor(u32);
+
+impl EaseFactor {
+    /// The lowest ease factor Anki allows
+    pub const MIN: EaseFactor = EaseFactor(1300);
+
+    /// Creates an ease factor
+    ///
+    /// # Arguments
+    ///
+    /// * `permille` - The ease factor in permille, at least 1300
+    ///
+    /// # Returns
+    ///
+    /// The ease factor, or a validation error if it is below Anki's minimum
+    pub fn new(permille: u32) -> Result<Self> {
+        if permille < Self::MIN.0 {
+            return Err(AnkiError::ValidationError(format!(
+                "Ease factor {} is below the minimum of {}",
+                permille,
+                Self::MIN.0
+            )));
+        }
+        Ok(Self(permille))
+    }
+
+    /// Gets the ease factor from a value reported by Anki, `None` for cards without one
+    pub(crate) fn from_anki(permille: u32) -> Option<Self> {
+        (permille > 0).then_some(Self(permille))
+    }
+
+    /// Gets the ease factor in permille
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The interval of a card
+///
+/// Anki stores intervals of reviewed cards in days and those of learning steps as
+/// negative numbers of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    /// An interval in days
+    Days(u64),
+    /// An interval of a learning step in seconds
+    Seconds(u64),
+}
+
+impl From<i64> for Interval {
+    fn from(value: i64) -> Self {
+        if value < 0 {
+            Interval::Seconds(value.unsigned_abs())
+        } else {
+            Interval::Days(value.unsigned_abs())
+        }
+    }
+}
+
+/// The learning stage of a card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardType {
+    New,
+    Learning,
+    Review,
+    Relearning,
+    /// A type this crate doesn't know about
+    Other(i8),
+}
+
+impl From<i8> for CardType {
+    fn from(value: i8) -> Self {
+        match value {
+            0 => CardType::New,
+            1 => CardType::Learning,
+            2 => CardType::Review,
+            3 => CardType::Relearning,
+            other => CardType::Other(other),
+        }
+    }
+}
+
+/// The queue a card is scheduled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardQueue {
+    /// Buried by the scheduler, e.g. as a sibling of a reviewed card
+    SchedulerBuried,
+    /// Buried by the user
+    UserBuried,
+    Suspended,
+    New,
+    /// In learning, due within the day
+    Learning,
+    Review,
+    /// In learning, due on a later day
+    DayLearning,
+    /// In a filtered deck in preview mode
+    Preview,
+    /// A queue this crate doesn't know about
+    Other(i8),
+}
+
+impl From<i8> for CardQueue {
+    fn from(value: i8) -> Self {
+        match value {
+            -3 => CardQueue::SchedulerBuried,
+            -2 => CardQueue::UserBuried,
+            -1 => CardQueue::Suspended,
+            0 => CardQueue::New,
+            1 => CardQueue::Learning,
+            2 => CardQueue::Review,
+            3 => CardQueue::DayLearning,
+            4 => CardQueue::Preview,
+            other => CardQueue::Other(other),
+        }
+    }
+}
+
+/// Represents a card in Anki, as returned by
+/// [`CardClient::get_info`](crate::client::CardClient::get_info)
 #[derive(Debug, Clone)]
 pub struct Card {
+    id: CardId,
+    note_id: NoteId,
+    deck_name: String,
     model: Model,
+    template_ord: usize,
     field_values: HashMap<String, String>,
     tags: HashSet<String>,
     media: Vec<(MediaType, MediaSource, String, Field)>,
+    question: String,
+    answer: String,
+    card_type: CardType,
+    queue: CardQueue,
+    due: i64,
+    interval: Interval,
+    ease_factor: Option<EaseFactor>,
+    reps: u32,
+    lapses: u32,
+    modified: u64,
 }
 
 impl Card {
-    /// Creates a new card with the given model and data
-    pub(crate) fn new(
-        model: Model,
-        field_values: HashMap<String, String>,
-        tags: HashSet<String>,
-        media: Vec<(MediaType, MediaSource, String, Field)>,
-    ) -> Self {
+    /// Creates a card from the result of `cardsInfo`, its model and the tags of its note
+    pub(crate) fn from_info(info: CardInfoDto, model: Model, tags: Vec<String>) -> Self {
         Self {
+            id: CardId(info.card_id),
+            note_id: NoteId(info.note),
+            deck_name: info.deck_name,
             model,
-            field_values,
-            tags,
-            media,
+            template_ord: info.ord,
+            field_values: info
+                .fields
+                .into_iter()
+                .map(|(name, field)| (name, field.value))
+                .collect(),
+            tags: tags.into_iter().collect(),
+            // AnkiConnect doesn't report which media a card references
+            media: Vec::new(),
+            question: info.question,
+            answer: info.answer,
+            card_type: info.card_type.into(),
+            queue: info.queue.into(),
+            due: info.due,
+            interval: info.interval.into(),
+            ease_factor: EaseFactor::from_anki(info.factor),
+            reps: info.reps,
+            lapses: info.lapses,
+            modified: info.modified,
         }
     }
 
+    /// Gets the ID of this card
+    pub fn id(&self) -> CardId {
+        self.id
+    }
+
+    /// Gets the ID of the note this card belongs to
+    pub fn note_id(&self) -> NoteId {
+        self.note_id
+    }
+
+    /// Gets the name of the deck this card is in
+    pub fn deck_name(&self) -> &str {
+        &self.deck_name
+    }
+
     /// Gets the model (note type) of this card
     pub fn model(&self) -> &Model {
         &self.model
     }
 
+    /// Gets the ordinal of the card template this card was generated from
+    pub fn template_ord(&self) -> usize {
+        self.template_ord
+    }
+
     /// Gets the field values of this card
     pub fn fields(&self) -> impl Iterator<Item = (&Field, &String)> {
         // Map field names to the actual Field objects
@@ -64,6 +235,8 @@ impl Card {
     }
 
     /// Gets media attached to this card
+    ///
+    /// This is empty for cards read from Anki, as AnkiConnect doesn't report media.
     pub fn media(&self) -> impl Iterator<Item = &(MediaType, MediaSource, String, Field)> {
         self.media.iter()
     }
@@ -86,4 +259,57 @@ impl Card {
             .back_field()
             .and_then(|field| self.field_values.get(field.name()))
     }
+
+    /// Gets the rendered HTML of the question side
+    pub fn question(&self) -> &str {
+        &self.question
+    }
+
+    /// Gets the rendered HTML of the answer side
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    /// Gets the learning stage of this card
+    pub fn card_type(&self) -> CardType {
+        self.card_type
+    }
+
+    /// Gets the queue this card is scheduled in
+    pub fn queue(&self) -> CardQueue {
+        self.queue
+    }
+
+    /// Gets when this card is due
+    ///
+    /// For new cards this is the position in the new queue, for review cards the day
+    /// relative to the collection creation and for learning cards a Unix timestamp.
+    pub fn due(&self) -> i64 {
+        self.due
+    }
+
+    /// Gets the current interval
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    /// Gets the ease factor, `None` for cards that were never reviewed
+    pub fn ease_factor(&self) -> Option<EaseFactor> {
+        self.ease_factor
+    }
+
+    /// Gets how often this card was reviewed
+    pub fn reps(&self) -> u32 {
+        self.reps
+    }
+
+    /// Gets how often this card was forgotten after it was learned
+    pub fn lapses(&self) -> u32 {
+        self.lapses
+    }
+
+    /// Gets when this card was last modified, in seconds since the Unix epoch
+    pub fn modified(&self) -> u64 {
+        self.modified
+    }
 }
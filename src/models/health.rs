@@ -0,0 +1,26 @@
+//! Health model definitions
+
+use std::time::Duration;
+
+/// A snapshot of the state of AnkiConnect, as returned by
+/// [`AnkiClient::health`](crate::AnkiClient::health)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Health {
+    /// Whether AnkiConnect answered at all
+    pub reachable: bool,
+    /// The round-trip time of the `version` request, if it succeeded
+    pub latency: Option<Duration>,
+    /// The version of the AnkiConnect plugin, if it could be determined
+    pub version: Option<u16>,
+    /// The name of the active profile, if it could be determined
+    pub profile: Option<String>,
+    /// The error that made the check fail, if any
+    pub error: Option<String>,
+}
+
+impl Health {
+    /// Checks if AnkiConnect is reachable and answers requests
+    pub fn is_healthy(&self) -> bool {
+        self.reachable && self.version.is_some()
+    }
+}
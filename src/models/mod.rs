@@ -4,15 +4,23 @@
 //! such as decks, cards, note types, and fields.
 
 // Declare submodules
+mod capabilities;
 mod card;
 mod deck;
+mod health;
 mod media;
 mod model;
 mod note;
+mod permission;
+mod schedule;
 
 // Re-export primary types
-pub use self::card::{Card, CardId};
+pub use self::capabilities::Capabilities;
+pub use self::card::{Card, CardId, CardQueue, CardType, EaseFactor, Interval};
 pub use self::deck::{Deck, DeckConfig, DeckId, DeckStats};
+pub use self::health::Health;
 pub use self::media::{FieldMedia, Media, MediaSource, MediaType};
 pub use self::model::{Field, FieldRef, Model, ModelId};
 pub use self::note::{Note, NoteId};
+pub use self::permission::Permission;
+pub use self::schedule::{DueDateSpec, Ease};
@@ -146,6 +146,7 @@ impl<'a> FieldRef<'a> {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Error, Debug)]
 pub enum ModelError {
     #[error("Model must have at least one field")]
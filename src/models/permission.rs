@@ -0,0 +1,37 @@
+//! Permission model definitions
+
+use crate::client::request::PermissionDto;
+
+/// The result of AnkiConnect's permission handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// The client may send requests
+    Granted {
+        /// Whether requests have to include an API key
+        require_api_key: bool,
+        /// The version of the AnkiConnect plugin
+        version: u16,
+    },
+    /// The user denied the request, or the origin is not trusted
+    Denied,
+}
+
+impl Permission {
+    /// Checks if permission was granted
+    pub fn is_granted(&self) -> bool {
+        matches!(self, Self::Granted { .. })
+    }
+}
+
+impl From<PermissionDto> for Permission {
+    fn from(dto: PermissionDto) -> Self {
+        if dto.permission == "granted" {
+            Self::Granted {
+                require_api_key: dto.require_apikey,
+                version: dto.version.unwrap_or_default(),
+            }
+        } else {
+            Self::Denied
+        }
+    }
+}
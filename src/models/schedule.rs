@@ -0,0 +1,136 @@
+//! Scheduling model definitions
+
+use crate::error::{AnkiError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// An answer to a card in review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ease {
+    Again = 1,
+    Hard = 2,
+    Good = 3,
+    Easy = 4,
+}
+
+/// When cards should become due, in the syntax of Anki's "Set Due Date" dialog
+///
+/// # Example
+///
+/// ```rust
+/// use ankiconnect_rs::DueDateSpec;
+///
+/// # fn main() -> ankiconnect_rs::Result<()> {
+/// assert_eq!(DueDateSpec::today().to_string(), "0");
+/// assert_eq!(DueDateSpec::between(1, 7)?.to_string(), "1-7");
+/// assert_eq!(DueDateSpec::in_days(3).with_interval().to_string(), "3!");
+/// assert_eq!("1-7".parse::<DueDateSpec>()?, DueDateSpec::between(1, 7)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DueDateSpec {
+    min_days: u32,
+    max_days: u32,
+    set_interval: bool,
+}
+
+impl DueDateSpec {
+    /// Makes the cards due today
+    pub fn today() -> Self {
+        Self::in_days(0)
+    }
+
+    /// Makes the cards due in the given number of days
+    pub fn in_days(days: u32) -> Self {
+        Self {
+            min_days: days,
+            max_days: days,
+            set_interval: false,
+        }
+    }
+
+    /// Makes each card due on a random day in the given range
+    ///
+    /// # Arguments
+    ///
+    /// * `min_days` - The earliest day, relative to today
+    /// * `max_days` - The latest day, relative to today
+    ///
+    /// # Returns
+    ///
+    /// The due date, or a validation error if the range is empty
+    pub fn between(min_days: u32, max_days: u32) -> Result<Self> {
+        if min_days > max_days {
+            return Err(AnkiError::ValidationError(format!(
+                "Invalid due date range {}-{}: the start is after the end",
+                min_days, max_days
+            )));
+        }
+        Ok(Self {
+            min_days,
+            max_days,
+            set_interval: false,
+        })
+    }
+
+    /// Also sets the interval of review cards to the new delay (the `!` suffix)
+    pub fn with_interval(mut self) -> Self {
+        self.set_interval = true;
+        self
+    }
+
+    /// Gets the earliest day the cards become due, relative to today
+    pub fn min_days(&self) -> u32 {
+        self.min_days
+    }
+
+    /// Gets the latest day the cards become due, relative to today
+    pub fn max_days(&self) -> u32 {
+        self.max_days
+    }
+
+    /// Checks if the interval of review cards is set to the new delay
+    pub fn sets_interval(&self) -> bool {
+        self.set_interval
+    }
+}
+
+impl fmt::Display for DueDateSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.min_days)?;
+        if self.max_days != self.min_days {
+            write!(f, "-{}", self.max_days)?;
+        }
+        if self.set_interval {
+            write!(f, "!")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DueDateSpec {
+    type Err = AnkiError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || AnkiError::ValidationError(format!("Invalid due date '{}'", s));
+
+        let (range, set_interval) = match s.trim().strip_suffix('!') {
+            Some(range) => (range, true),
+            None => (s.trim(), false),
+        };
+        let spec = match range.split_once('-') {
+            Some((min, max)) => Self::between(
+                min.trim().parse().map_err(|_| invalid())?,
+                max.trim().parse().map_err(|_| invalid())?,
+            )?,
+            None => Self::in_days(range.parse().map_err(|_| invalid())?),
+        };
+
+        Ok(if set_interval {
+            spec.with_interval()
+        } else {
+            spec
+        })
+    }
+}
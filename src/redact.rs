@@ -0,0 +1,94 @@
+//! Summaries of action parameters that are safe to log or show in errors
+
+use serde_json::{Map, Value};
+
+/// Keys whose values are never shown
+const SECRET_KEYS: &[&str] = &["key", "apiKey"];
+
+/// Keys holding base64 encoded media
+const MEDIA_KEYS: &[&str] = &["data"];
+
+/// Strings longer than this are truncated
+const MAX_STRING_CHARS: usize = 64;
+
+/// Arrays longer than this are truncated
+const MAX_ARRAY_ITEMS: usize = 10;
+
+/// Summaries longer than this are truncated
+const MAX_SUMMARY_CHARS: usize = 512;
+
+/// Summarizes the parameters of an action
+///
+/// API keys are replaced by a placeholder, base64 media by its length, and long
+/// strings and arrays are truncated.
+pub(crate) fn summarize_params(params: &Value) -> String {
+    let summary = redact(params).to_string();
+    truncate(&summary, MAX_SUMMARY_CHARS)
+}
+
+/// Redacts secrets and media from a JSON value, truncating long strings and arrays
+pub(crate) fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let redacted: Map<String, Value> = object
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        _ if SECRET_KEYS.contains(&key.as_str()) => {
+                            Value::String("<redacted>".to_string())
+                        }
+                        Value::String(data) if MEDIA_KEYS.contains(&key.as_str()) => {
+                            Value::String(format!("<{} bytes of base64>", data.len()))
+                        }
+                        value => redact(value),
+                    };
+                    (key.clone(), value)
+                })
+                .collect();
+            Value::Object(redacted)
+        }
+        Value::Array(items) => {
+            let mut redacted: Vec<Value> = items.iter().take(MAX_ARRAY_ITEMS).map(redact).collect();
+            if items.len() > MAX_ARRAY_ITEMS {
+                redacted.push(Value::String(format!(
+                    "... {} more",
+                    items.len() - MAX_ARRAY_ITEMS
+                )));
+            }
+            Value::Array(redacted)
+        }
+        Value::String(text) => Value::String(truncate(text, MAX_STRING_CHARS)),
+        value => value.clone(),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summarize_params_redacts_secrets_and_media() {
+        let params = json!({
+            "key": "secret",
+            "filename": "hello.txt",
+            "data": "aGVsbG8=",
+            "notes": (0..12).collect::<Vec<_>>(),
+        });
+
+        let summary = summarize_params(&params);
+
+        assert!(!summary.contains("secret"));
+        assert!(!summary.contains("aGVsbG8="));
+        assert!(summary.contains("<8 bytes of base64>"));
+        assert!(summary.contains("hello.txt"));
+        assert!(summary.contains("... 2 more"));
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(feature = "fake")]
+use std::sync::Arc;
+
+use ankiconnect_rs::client::action::Version;
+#[cfg(feature = "fake")]
+use ankiconnect_rs::{fake::FakeAnki, AnkiConnectError, AnkiError};
+use ankiconnect_rs::{Action, AnkiClient, Result};
+use httpmock::prelude::*;
+use serde::Serialize;
+use serde_json::json;
+
+// Helper function to create a mock AnkiClient connected to the given mock server
+fn create_mock_client(server: &MockServer) -> AnkiClient {
+    AnkiClient::with_connection(&server.host(), server.port())
+}
+
+/// An action that isn't wrapped by the crate
+struct GetEaseFactors;
+
+#[derive(Serialize)]
+struct CardsParams {
+    cards: Vec<u64>,
+}
+
+impl Action for GetEaseFactors {
+    const NAME: &'static str = "getEaseFactors";
+    type Params = CardsParams;
+    type Response = Vec<u32>;
+}
+
+#[test]
+fn test_execute_custom_action() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "getEaseFactors",
+            "version": 6,
+            "params": {"cards": [1483959291685_u64, 1483959293217_u64]}
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": [4100, 3900], "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let factors = client.execute::<GetEaseFactors>(CardsParams {
+        cards: vec![1483959291685, 1483959293217],
+    });
+
+    // Assert
+    mock.assert();
+    assert_eq!(factors?, vec![4100, 3900]);
+
+    Ok(())
+}
+
+#[test]
+fn test_execute_sends_unit_params_as_none() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "version",
+            "version": 6
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 6, "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let version = client.execute::<Version>(());
+
+    // Assert
+    mock.assert();
+    assert_eq!(version?, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_action() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "guiCurrentCard",
+            "version": 6,
+            "params": {"verbose": true}
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": null, "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client.raw("guiCurrentCard", json!({"verbose": true}));
+
+    // Assert
+    mock.assert();
+    assert_eq!(result?, serde_json::Value::Null);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_raw_action_reports_errors() {
+    // Arrange
+    let client = AnkiClient::with_sender(Arc::new(FakeAnki::new()));
+
+    // Act
+    let unsupported = client.raw("pluginAction", serde_json::Value::Null);
+    let invalid = client.raw("deckNames", json!([1, 2]));
+
+    // Assert
+    assert!(matches!(
+        unsupported,
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::UnsupportedAction,
+            ..
+        })
+    ));
+    assert!(matches!(invalid, Err(AnkiError::ValidationError(_))));
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_batch_with_typed_actions() -> Result<()> {
+    // Arrange
+    let client = AnkiClient::with_sender(Arc::new(FakeAnki::new()));
+    let mut batch = client.batch();
+    let version = batch.add_action::<Version>(())?;
+
+    // Act
+    let results = batch.send()?;
+
+    // Assert
+    assert_eq!(results.get(&version)?, 6);
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use ankiconnect_rs::{AnkiClient, AnkiConnectError, AnkiError, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_api_key_is_sent_with_requests() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let version_mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "version",
+            "version": 6,
+            "key": "secret"
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": 6,
+                "error": null
+            }));
+    });
+    let client = AnkiClient::with_api_key(&server.host(), server.port(), "secret");
+
+    // Act
+    let version = client.version()?;
+
+    // Assert
+    version_mock.assert();
+    assert_eq!(version, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_api_key_is_sent_with_each_batch_action() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let multi_mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "multi",
+            "version": 6,
+            "key": "secret",
+            "params": {
+                "actions": [
+                    {"action": "version", "version": 6, "key": "secret"},
+                    {"action": "deckNames", "version": 6, "key": "secret"}
+                ]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [
+                    {"result": 6, "error": null},
+                    {"result": ["Default"], "error": null}
+                ],
+                "error": null
+            }));
+    });
+    let client = AnkiClient::with_api_key(&server.host(), server.port(), "secret");
+
+    // Act
+    let mut batch = client.batch();
+    let version = batch.add::<(), u16>("version", None)?;
+    let decks = batch.add::<(), Vec<String>>("deckNames", None)?;
+    let results = batch.send()?;
+
+    // Assert
+    multi_mock.assert();
+    assert_eq!(results.get(&version)?, 6);
+    assert_eq!(results.get(&decks)?, vec!["Default"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_api_key_error() {
+    // Arrange
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/");
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": null,
+                "error": "valid api key must be provided"
+            }));
+    });
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    // Act
+    let result = client.version();
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::InvalidApiKey,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "async")]
+
+use ankiconnect_rs::builders::Query;
+use ankiconnect_rs::{AnkiConnectError, AnkiError, AsyncAnkiClient, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+
+// Helper function to create a mock AsyncAnkiClient connected to the given mock server
+fn create_mock_client(server: &MockServer) -> AsyncAnkiClient {
+    AsyncAnkiClient::with_connection(&server.host(), server.port())
+}
+
+#[tokio::test]
+async fn test_find_cards() -> Result<()> {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/").json_body(json!({
+                "action": "findCards",
+                "version": 6,
+                "params": {
+                    "query": "deck:current"
+                }
+            }));
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "result": [1494723142483_u64, 1494703460437_u64],
+                    "error": null
+                }));
+        })
+        .await;
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let cards = client
+        .cards()
+        .find(&Query::custom("deck:current".to_string()))
+        .await;
+
+    // Assert
+    mock.assert_async().await;
+
+    let cards = cards?;
+    assert_eq!(cards.len(), 2);
+    assert_eq!(cards[0].value(), 1494723142483);
+    assert_eq!(cards[1].value(), 1494703460437);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_all_decks() -> Result<()> {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/").json_body(json!({
+                "action": "deckNamesAndIds",
+                "version": 6
+            }));
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "result": {"Default": 1, "Japanese::Vocabulary": 1519323742721_u64},
+                    "error": null
+                }));
+        })
+        .await;
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let decks = client.decks().get_all().await;
+
+    // Assert
+    mock.assert_async().await;
+
+    let decks = decks?;
+    assert_eq!(decks.len(), 2);
+    assert!(decks.iter().any(|d| d.name() == "Japanese::Vocabulary"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_media_file_empty_result() -> Result<()> {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/").json_body(json!({
+                "action": "deleteMediaFile",
+                "version": 6,
+                "params": {
+                    "filename": "_hello.txt"
+                }
+            }));
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "result": null,
+                    "error": null
+                }));
+        })
+        .await;
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client.media().delete_file("_hello.txt").await;
+
+    // Assert
+    mock.assert_async().await;
+    result
+}
+
+#[tokio::test]
+async fn test_deck_not_found_error() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/").json_body(json!({
+                "action": "getDeckStats",
+                "version": 6,
+                "params": {
+                    "decks": ["Default"]
+                }
+            }));
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "result": null,
+                    "error": "deck was not found: Default"
+                }));
+        })
+        .await;
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client.decks().get_stat("Default").await;
+
+    // Assert
+    mock.assert_async().await;
+    assert!(matches!(
+        result.unwrap_err(),
+        AnkiError::ActionFailed {
+            error: AnkiConnectError::DeckNotFound(_),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_unsupported_action_fails_fast() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"action": "version"}"#);
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({"result": 6, "error": null}));
+        })
+        .await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"action": "apiReflect"}"#);
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "result": {"scopes": ["actions"], "actions": ["apiReflect", "version"]},
+                    "error": null
+                }));
+        })
+        .await;
+
+    let client = create_mock_client(&server);
+    client.capabilities().await.unwrap();
+
+    // Act
+    let result = client
+        .cards()
+        .find(&Query::custom("deck:current".to_string()))
+        .await;
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionNotSupported { ref action, version: 6 }) if action == "findCards"
+    ));
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use ankiconnect_rs::{AnkiClient, AnkiConnectError, AnkiError, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+
+// Helper function to create a mock AnkiClient connected to the given mock server
+fn create_mock_client(server: &MockServer) -> AnkiClient {
+    AnkiClient::with_connection(&server.host(), server.port())
+}
+
+#[test]
+fn test_batch_returns_results_per_action() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "multi",
+            "version": 6,
+            "params": {
+                "actions": [
+                    {
+                        "action": "deckNamesAndIds",
+                        "version": 6
+                    },
+                    {
+                        "action": "createDeck",
+                        "version": 6,
+                        "params": {
+                            "deck": "Japanese::Tokyo"
+                        }
+                    },
+                    {
+                        "action": "getDeckStats",
+                        "version": 6,
+                        "params": {
+                            "decks": ["Missing"]
+                        }
+                    }
+                ]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [
+                    {"result": {"Default": 1}, "error": null},
+                    {"result": 1519323742721_u64, "error": null},
+                    {"result": null, "error": "deck was not found: Missing"}
+                ],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let mut batch = client.batch();
+    let decks = batch.add::<(), HashMap<String, u64>>("deckNamesAndIds", None)?;
+    let created = batch.add::<_, u64>("createDeck", Some(json!({"deck": "Japanese::Tokyo"})))?;
+    let stats =
+        batch.add::<_, serde_json::Value>("getDeckStats", Some(json!({"decks": ["Missing"]})))?;
+    let results = batch.send();
+
+    // Assert
+    mock.assert();
+
+    let results = results?;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.get(&decks)?.get("Default"), Some(&1));
+    assert_eq!(results.get(&created)?, 1519323742721);
+    assert!(matches!(
+        results.get(&stats),
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::DeckNotFound(_),
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_batch_sends_no_request() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/");
+        then.status(500);
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let batch = client.batch();
+    assert!(batch.is_empty());
+    let results = batch.send()?;
+
+    // Assert
+    mock.assert_hits(0);
+    assert!(results.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_handles_of_other_batches_are_rejected() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "multi"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [{"result": 6, "error": null}],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    let mut other = client.batch();
+    let foreign = other.add::<(), String>("getProfiles", None)?;
+
+    // Act
+    let mut batch = client.batch();
+    let version = batch.add::<(), u16>("version", None)?;
+    let results = batch.send()?;
+
+    // Assert
+    mock.assert();
+    assert_eq!(results.get(&version)?, 6);
+    assert!(matches!(
+        results.get(&foreign),
+        Err(AnkiError::ValidationError(_))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use ankiconnect_rs::{AnkiClient, AnkiError, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+
+// Helper function to mock the version action on the given path
+fn mock_version<'a>(server: &'a MockServer, path: &str) -> httpmock::Mock<'a> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(path)
+            .header("user-agent", "anki-sync/1.0")
+            .json_body(json!({
+                "action": "version",
+                "version": 6,
+                "key": "secret"
+            }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": 6,
+                "error": null
+            }));
+    })
+}
+
+#[test]
+fn test_builder_uses_full_url() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let version_mock = mock_version(&server, "/anki");
+    let client = AnkiClient::builder()
+        .url(server.url("/anki"))
+        .api_key("secret")
+        .user_agent("anki-sync/1.0")
+        .build()?;
+
+    // Act
+    let version = client.version()?;
+
+    // Assert
+    version_mock.assert();
+    assert_eq!(version, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_rejects_invalid_url() {
+    // Act
+    let result = AnkiClient::builder().url("localhost:8765").build();
+
+    // Assert
+    assert!(matches!(result, Err(AnkiError::ValidationError(_))));
+}
+
+#[test]
+fn test_from_env() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let version_mock = server.mock(|when, then| {
+        when.method(POST).path("/env").json_body(json!({
+            "action": "version",
+            "version": 6,
+            "key": "from-env"
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": 6,
+                "error": null
+            }));
+    });
+    std::env::set_var("ANKICONNECT_URL", server.url("/env"));
+    std::env::set_var("ANKICONNECT_KEY", "from-env");
+
+    // Act
+    let client = AnkiClient::from_env()?;
+    let version = client.version()?;
+
+    // Assert
+    version_mock.assert();
+    assert_eq!(version, 6);
+
+    Ok(())
+}
+
+#[cfg(feature = "config-file")]
+#[test]
+fn test_config_file() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let version_mock = mock_version(&server, "/config");
+    let path = std::env::temp_dir().join(format!("ankiconnect-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        format!(
+            "url = \"{}\"\napi_key = \"secret\"\nuser_agent = \"anki-sync/1.0\"\nread_timeout_ms = 5000\n",
+            server.url("/config")
+        ),
+    )
+    .unwrap();
+
+    // Act
+    let client = AnkiClient::builder().config_file(&path)?.build()?;
+    let version = client.version()?;
+
+    // Assert
+    version_mock.assert();
+    assert_eq!(version, 6);
+
+    std::fs::remove_file(path).unwrap();
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use ankiconnect_rs::cache::CachingSender;
+use ankiconnect_rs::{AnkiClient, AnkiConnectResponse, RequestSender, Result};
+use httpmock::prelude::*;
+use serde_json::{json, Value};
+
+// Helper function to create a caching AnkiClient connected to the given mock server
+fn create_cached_client(server: &MockServer, ttl: Duration) -> AnkiClient {
+    AnkiClient::with_connection(&server.host(), server.port()).cached(ttl)
+}
+
+/// A sender that holds the first deck lookup until the test releases it
+struct GatedStub {
+    started: Mutex<mpsc::Sender<()>>,
+    gate: Mutex<Option<mpsc::Receiver<()>>>,
+    lookups: Mutex<usize>,
+}
+
+impl RequestSender for GatedStub {
+    fn send_raw(&self, action: &str, _params: Option<Value>) -> Result<AnkiConnectResponse> {
+        if action != "deckNamesAndIds" {
+            return Ok(AnkiConnectResponse::success(json!(2)));
+        }
+        *self.lookups.lock().unwrap() += 1;
+        if let Some(gate) = self.gate.lock().unwrap().take() {
+            self.started.lock().unwrap().send(()).unwrap();
+            gate.recv().unwrap();
+        }
+        Ok(AnkiConnectResponse::success(json!({"Default": 1})))
+    }
+}
+
+#[test]
+fn test_deck_lookups_are_cached() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let decks_mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "deckNamesAndIds",
+            "version": 6
+        }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": {"Default": 1}, "error": null}));
+    });
+    let client = create_cached_client(&server, Duration::from_secs(60));
+
+    // Act
+    let by_name = client.decks().get_by_name("Default")?;
+    let exists = client.decks().exists("Default")?;
+    let by_id = client.decks().get_by_id(ankiconnect_rs::DeckId(1))?;
+
+    // Assert
+    decks_mock.assert_hits(1);
+    assert!(by_name.is_some());
+    assert!(exists);
+    assert!(by_id.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_mutating_calls_invalidate_cache() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let decks_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "deckNamesAndIds"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": {"Default": 1}, "error": null}));
+    });
+    let create_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "createDeck"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 2, "error": null}));
+    });
+    let client = create_cached_client(&server, Duration::from_secs(60));
+    client.decks().get_all()?;
+
+    // Act
+    client.decks().create("New")?;
+    client.decks().get_all()?;
+    client.invalidate_cache();
+    client.decks().get_all()?;
+
+    // Assert
+    create_mock.assert_hits(1);
+    decks_mock.assert_hits(3);
+
+    Ok(())
+}
+
+#[test]
+fn test_field_font_change_invalidates_model_details() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let models_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "findModelsByName"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [{
+                    "id": 1483883011648_u64,
+                    "name": "Basic",
+                    "flds": [
+                        {"name": "Front", "ord": 0},
+                        {"name": "Back", "ord": 1}
+                    ]
+                }],
+                "error": null
+            }));
+    });
+    let font_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "modelFieldSetFont"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": null, "error": null}));
+    });
+    let client = create_cached_client(&server, Duration::from_secs(60));
+    client.models().get_by_name("Basic")?;
+
+    // Act
+    client.raw(
+        "modelFieldSetFont",
+        json!({"modelName": "Basic", "fieldName": "Front", "font": "Courier"}),
+    )?;
+    let model = client.models().get_by_name("Basic")?;
+
+    // Assert
+    font_mock.assert_hits(1);
+    models_mock.assert_hits(2);
+    assert!(model.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_all_models_are_cached() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let names = [
+        "Basic",
+        "Basic (and reversed card)",
+        "Cloze",
+        "Image Occlusion",
+        "Vocab",
+    ];
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "modelNamesAndIds"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": names
+                    .iter()
+                    .zip(1_u64..)
+                    .map(|(name, id)| (name.to_string(), json!(id)))
+                    .collect::<serde_json::Map<_, _>>(),
+                "error": null
+            }));
+    });
+    let models_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "findModelsById"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": names
+                    .iter()
+                    .zip(1_u64..)
+                    .map(|(name, id)| json!({
+                        "id": id,
+                        "name": name,
+                        "flds": [{"name": "Front", "ord": 0}]
+                    }))
+                    .collect::<Vec<_>>(),
+                "error": null
+            }));
+    });
+    let client = create_cached_client(&server, Duration::from_secs(60));
+
+    // Act
+    let first = client.models().get_all()?;
+    let second = client.models().get_all()?;
+
+    // Assert
+    models_mock.assert_hits(1);
+    assert_eq!(first.len(), names.len());
+    assert_eq!(second.len(), names.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_entries_expire() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let names_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "modelFieldNames"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": ["Front", "Back"], "error": null}));
+    });
+    let client = create_cached_client(&server, Duration::from_millis(50));
+    client.models().get_fields_for_name("Basic")?;
+
+    // Act
+    client.models().get_fields_for_name("Basic")?;
+    std::thread::sleep(Duration::from_millis(100));
+    let fields = client.models().get_fields_for_name("Basic")?;
+
+    // Assert
+    names_mock.assert_hits(2);
+    assert_eq!(fields, vec!["Front", "Back"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_caching_again_replaces_the_cache() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    let names_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "modelFieldNames"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": ["Front", "Back"], "error": null}));
+    });
+    let client =
+        create_cached_client(&server, Duration::from_secs(60)).cached(Duration::from_millis(50));
+    client.models().get_fields_for_name("Basic")?;
+
+    // Act
+    std::thread::sleep(Duration::from_millis(100));
+    client.models().get_fields_for_name("Basic")?;
+
+    // Assert
+    names_mock.assert_hits(2);
+
+    Ok(())
+}
+
+#[test]
+fn test_lookup_racing_a_change_is_not_cached() -> Result<()> {
+    // Arrange
+    let (started_tx, started) = mpsc::channel();
+    let (release, gate) = mpsc::channel();
+    let stub = Arc::new(GatedStub {
+        started: Mutex::new(started_tx),
+        gate: Mutex::new(Some(gate)),
+        lookups: Mutex::new(0),
+    });
+    let cache = Arc::new(CachingSender::new(stub.clone(), Duration::from_secs(60)));
+    let lookup = {
+        let cache = Arc::clone(&cache);
+        thread::spawn(move || cache.send_raw("deckNamesAndIds", None))
+    };
+    started.recv().unwrap();
+
+    // Act
+    cache.send_raw("createDeck", Some(json!({"deck": "New"})))?;
+    release.send(()).unwrap();
+    lookup.join().unwrap()?;
+    cache.send_raw("deckNamesAndIds", None)?;
+
+    // Assert
+    assert_eq!(*stub.lookups.lock().unwrap(), 2);
+
+    Ok(())
+}
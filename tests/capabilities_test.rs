@@ -0,0 +1,332 @@
+#[cfg(feature = "fake")]
+use std::sync::Arc;
+
+#[cfg(feature = "fake")]
+use ankiconnect_rs::fake::FakeAnki;
+use ankiconnect_rs::{AnkiClient, AnkiError, QueryBuilder, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+
+// Helper function to create a mock AnkiClient connected to the given mock server
+fn create_mock_client(server: &MockServer) -> AnkiClient {
+    AnkiClient::with_connection(&server.host(), server.port())
+}
+
+// Helper function to mock the `version` request
+fn mock_version(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body(json!({"action": "version", "version": 6}));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 6, "error": null}));
+    });
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_capabilities_of_fake() -> Result<()> {
+    // Arrange
+    let client = AnkiClient::with_sender(Arc::new(FakeAnki::new()));
+
+    // Act
+    let capabilities = client.capabilities()?;
+
+    // Assert
+    assert_eq!(capabilities.version(), 6);
+    assert!(capabilities.supports("findCards"));
+    assert!(!capabilities.supports("guiAddCards"));
+    assert!(capabilities.actions().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_action_fails_fast() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_version(&server);
+
+    let reflect = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "apiReflect",
+            "version": 6,
+            "params": {"scopes": ["actions"], "actions": null}
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": {"scopes": ["actions"], "actions": ["apiReflect", "version"]},
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+    client.capabilities()?;
+
+    // Act
+    let result = client.cards().find(&QueryBuilder::deck("Default").build());
+    let capabilities = client.capabilities()?;
+
+    // Assert
+    reflect.assert_hits(1);
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionNotSupported { ref action, version: 6 }) if action == "findCards"
+    ));
+    assert!(!capabilities.supports("findCards"));
+
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_are_loaded_once_anki_answered() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_version(&server);
+
+    let reflect = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "apiReflect"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": {"scopes": ["actions"], "actions": ["apiReflect", "createDeck", "version"]},
+                "error": null
+            }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "createDeck"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 1519323742721_u64, "error": null}));
+    });
+    let find = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "findCards"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": [], "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let created = client.decks().create("Spanish");
+    let found = client.cards().find(&QueryBuilder::deck("Spanish").build());
+
+    // Assert
+    assert_eq!(created?.0, 1519323742721);
+    assert!(matches!(
+        found,
+        Err(AnkiError::ActionNotSupported { ref action, .. }) if action == "findCards"
+    ));
+    reflect.assert_hits(1);
+    create.assert_hits(1);
+    find.assert_hits(0);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_capability_loading_is_not_repeated_per_request() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let version = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body(json!({"action": "version", "version": 6}));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": null, "error": "collection is not available"}));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "createDeck"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 1519323742721_u64, "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    client.decks().create("Spanish")?;
+    client.decks().create("French")?;
+    let explicit = client.capabilities();
+
+    // Assert
+    create.assert_hits(2);
+    // Once by the second request and once by the explicit call
+    version.assert_hits(2);
+    assert!(explicit.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_are_not_loaded_while_anki_is_unreachable() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let version = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body(json!({"action": "version", "version": 6}));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": 6, "error": null}));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "createDeck"}"#);
+
+        then.status(503);
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let first = client.decks().create("Spanish");
+    let second = client.decks().create("French");
+
+    // Assert
+    assert!(first.is_err());
+    assert!(second.is_err());
+    create.assert_hits(2);
+    version.assert_hits(0);
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_sends_actions_missing_from_api_reflect() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_version(&server);
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "apiReflect"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": {"scopes": ["actions"], "actions": ["apiReflect", "version"]},
+                "error": null
+            }));
+    });
+    let plugin = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "pluginAction"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": "done", "error": null}));
+    });
+
+    let client = create_mock_client(&server);
+    client.capabilities()?;
+
+    // Act
+    let result = client.raw("pluginAction", json!({"answer": 42}))?;
+
+    // Assert
+    plugin.assert();
+    assert_eq!(result, json!("done"));
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_sends_actions_missing_from_api_reflect() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_version(&server);
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "apiReflect"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": {"scopes": ["actions"], "actions": ["apiReflect", "version"]},
+                "error": null
+            }));
+    });
+    let multi = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "multi"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [{"result": "done", "error": null}],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+    client.capabilities()?;
+
+    // Act
+    let mut batch = client.batch();
+    let handle = batch.add::<(), String>("pluginAction", None)?;
+    let results = batch.send()?;
+
+    // Assert
+    multi.assert();
+    assert_eq!(results.get(&handle)?, "done");
+
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_without_api_reflect() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_version(&server);
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "apiReflect"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": null, "error": "unsupported action"}));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let capabilities = client.capabilities()?;
+
+    // Assert
+    assert_eq!(capabilities.version(), 6);
+    assert!(capabilities.actions().is_none());
+    assert!(capabilities.supports("findCards"));
+
+    Ok(())
+}
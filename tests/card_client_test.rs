@@ -1,5 +1,8 @@
 use ankiconnect_rs::builders::Query;
-use ankiconnect_rs::{AnkiClient, Result};
+use ankiconnect_rs::{
+    AnkiClient, AnkiError, CardId, CardQueue, CardType, DueDateSpec, Ease, EaseFactor,
+    ForgetOptions, Interval, Result,
+};
 use httpmock::prelude::*;
 use serde_json::json;
 
@@ -49,347 +52,637 @@ fn test_find_cards() -> Result<()> {
     Ok(())
 }
 
-// #[test]
-// fn test_get_ease_factors() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "getEaseFactors",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1483959291685, 1483959293217]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": [4100, 3900],
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let ease_factors = client.cards().get_ease_factors(&[CardId(1483959291685), CardId(1483959293217)]);
-//
-//     // Assert
-//     mock.assert();
-//
-//     let ease_factors = ease_factors?;
-//     assert_eq!(ease_factors.len(), 2);
-//     assert_eq!(ease_factors[0], 4100);
-//     assert_eq!(ease_factors[1], 3900);
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_set_ease_factors() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "setEaseFactors",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1483959291685, 1483959293217],
-//                     "easeFactors": [4100, 3900]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": [true, true],
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let results = client.cards().set_ease_factors(
-//         &[CardId(1483959291685), CardId(1483959293217)],
-//         &[4100, 3900]
-//     );
-//
-//     // Assert
-//     mock.assert();
-//
-//     let results = results?;
-//     assert_eq!(results.len(), 2);
-//     assert!(results[0]);
-//     assert!(results[1]);
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_suspend_cards() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "suspend",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1483959291685_u64, 1483959293217_u64]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": true,
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let result = client.cards().suspend_cards(&[CardId(1483959291685), CardId(1483959293217)]);
-//
-//     // Assert
-//     mock.assert();
-//     assert!(result.is_ok());
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_unsuspend_cards() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "unsuspend",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1483959291685_u64, 1483959293217_u64]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": true,
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let result = client.cards().unsuspend_cards(&[CardId(1483959291685), CardId(1483959293217)]);
-//
-//     // Assert
-//     mock.assert();
-//     assert!(result.is_ok());
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_are_cards_suspended() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "areSuspended",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1483959291685, 1483959293217, 1234567891234]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": [false, true, null],
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let results = client.cards().are_suspended(&[
-//         CardId(1483959291685),
-//         CardId(1483959293217),
-//         CardId(1234567891234)
-//     ]);
-//
-//     // Assert
-//     mock.assert();
-//
-//     let results = results?;
-//     assert_eq!(results.len(), 3);
-//     assert_eq!(results[0], Some(false));
-//     assert_eq!(results[1], Some(true));
-//     assert_eq!(results[2], None);
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_get_intervals() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "getIntervals",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1502298033753, 1502298036657],
-//                     "complete": true
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": [
-//                     [-120, -180, -240, -300, -360, -14400],
-//                     [-120, -180, -240, -300, -360, -14400, 1, 3]
-//                 ],
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let intervals = client.cards().get_intervals(
-//         &[CardId(1502298033753), CardId(1502298036657)],
-//         true
-//     );
-//
-//     // Assert
-//     mock.assert();
-//
-//     let intervals = intervals?;
-//     assert_eq!(intervals.len(), 2);
-//     assert_eq!(intervals[0], vec![-120, -180, -240, -300, -360, -14400]);
-//     assert_eq!(intervals[1], vec![-120, -180, -240, -300, -360, -14400, 1, 3]);
-//
-//     Ok(())
-// }
-
-// #[test]
-// fn test_cards_info() -> Result<()> {
-//     // Arrange
-//     let server = MockServer::start();
-//
-//     let mock = server.mock(|when, then| {
-//         when.method(POST)
-//             .path("/")
-//             .json_body(json!({
-//                 "action": "cardsInfo",
-//                 "version": 6,
-//                 "params": {
-//                     "cards": [1498938915662, 1502098034048]
-//                 }
-//             }));
-//
-//         then.status(200)
-//             .header("content-type", "application/json")
-//             .json_body(json!({
-//                 "result": [
-//                     {
-//                         "answer": "back content",
-//                         "question": "front content",
-//                         "deckName": "Default",
-//                         "modelName": "Basic",
-//                         "fieldOrder": 1,
-//                         "fields": {
-//                             "Front": {"value": "front content", "order": 0},
-//                             "Back": {"value": "back content", "order": 1}
-//                         },
-//                         "css": "p {font-family:Arial;}",
-//                         "cardId": 1498938915662,
-//                         "interval": 16,
-//                         "note": 1502298033753,
-//                         "ord": 1,
-//                         "type": 0,
-//                         "queue": 0,
-//                         "due": 1,
-//                         "reps": 1,
-//                         "lapses": 0,
-//                         "left": 6,
-//                         "mod": 1629454092
-//                     },
-//                     {
-//                         "answer": "back content",
-//                         "question": "front content",
-//                         "deckName": "Default",
-//                         "modelName": "Basic",
-//                         "fieldOrder": 0,
-//                         "fields": {
-//                             "Front": {"value": "front content", "order": 0},
-//                             "Back": {"value": "back content", "order": 1}
-//                         },
-//                         "css": "p {font-family:Arial;}",
-//                         "cardId": 1502098034048,
-//                         "interval": 23,
-//                         "note": 1502298033753,
-//                         "ord": 1,
-//                         "type": 0,
-//                         "queue": 0,
-//                         "due": 1,
-//                         "reps": 1,
-//                         "lapses": 0,
-//                         "left": 6
-//                     }
-//                 ],
-//                 "error": null
-//             }));
-//     });
-//
-//     let client = create_mock_client(&server);
-//
-//     // Act
-//     let cards_info = client.cards().get_cards_info(&[CardId(1498938915662), CardId(1502098034048)]);
-//
-//     // Assert
-//     mock.assert();
-//
-//     let cards_info = cards_info?;
-//     assert_eq!(cards_info.len(), 2);
-//     assert_eq!(cards_info[0].card_id.value(), 1498938915662);
-//     assert_eq!(cards_info[0].deck_name, "Default");
-//     assert_eq!(cards_info[0].model_name, "Basic");
-//     assert_eq!(cards_info[0].question, "front content");
-//     assert_eq!(cards_info[0].answer, "back content");
-//     assert_eq!(cards_info[0].interval, 16);
-//
-//     assert_eq!(cards_info[1].card_id.value(), 1502098034048);
-//     assert_eq!(cards_info[1].deck_name, "Default");
-//     assert_eq!(cards_info[1].model_name, "Basic");
-//     assert_eq!(cards_info[1].question, "front content");
-//     assert_eq!(cards_info[1].answer, "back content");
-//     assert_eq!(cards_info[1].interval, 23);
-//
-//     Ok(())
-// }
+#[test]
+fn test_get_ease_factors() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "getEaseFactors",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64, 1483959293218_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [4100, 0, null],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let ease_factors = client.cards().get_ease_factors(&[
+        CardId(1483959291685),
+        CardId(1483959293217),
+        CardId(1483959293218),
+    ]);
+
+    // Assert
+    mock.assert();
+
+    let ease_factors = ease_factors?;
+    assert_eq!(ease_factors.len(), 2);
+    assert_eq!(
+        ease_factors[&CardId(1483959291685)],
+        Some(EaseFactor::new(4100)?)
+    );
+    assert_eq!(ease_factors[&CardId(1483959293217)], None);
+    assert!(!ease_factors.contains_key(&CardId(1483959293218)));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_ease_factors() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "setEaseFactors",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64],
+                "easeFactors": [4100, 3900]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [true, true],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let results = client.cards().set_ease_factors(&[
+        (CardId(1483959291685), EaseFactor::new(4100)?),
+        (CardId(1483959293217), EaseFactor::new(3900)?),
+    ]);
+
+    // Assert
+    mock.assert();
+
+    let results = results?;
+    assert_eq!(results.len(), 2);
+    assert!(results[0]);
+    assert!(results[1]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ease_factor_below_minimum_is_rejected() {
+    // Act
+    let result = EaseFactor::new(1299);
+
+    // Assert
+    assert!(matches!(result, Err(AnkiError::ValidationError(_))));
+    assert_eq!(EaseFactor::new(1300).unwrap(), EaseFactor::MIN);
+}
+
+#[test]
+fn test_suspend_cards() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "suspend",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": true,
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client
+        .cards()
+        .suspend_cards(&[CardId(1483959291685), CardId(1483959293217)]);
+
+    // Assert
+    mock.assert();
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuspend_cards() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "unsuspend",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": true,
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client
+        .cards()
+        .unsuspend_cards(&[CardId(1483959291685), CardId(1483959293217)]);
+
+    // Assert
+    mock.assert();
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_forget_cards() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "forgetCards",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64],
+                "resetCounts": true
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": null,
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client.cards().forget_cards(
+        &[CardId(1483959291685)],
+        ForgetOptions {
+            reset_counts: true,
+            ..Default::default()
+        },
+    );
+
+    // Assert
+    mock.assert();
+    result?;
+
+    Ok(())
+}
+
+#[test]
+fn test_set_due_date() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "setDueDate",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64],
+                "days": "3-7!"
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": true,
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let result = client.cards().set_due_date(
+        &[CardId(1483959291685), CardId(1483959293217)],
+        DueDateSpec::between(3, 7)?.with_interval(),
+    );
+
+    // Assert
+    mock.assert();
+    result?;
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_due_date_spec() -> Result<()> {
+    // Act
+    let today: DueDateSpec = "0".parse()?;
+    let fixed: DueDateSpec = "3!".parse()?;
+    let reversed = "7-1".parse::<DueDateSpec>();
+    let garbage = "soon".parse::<DueDateSpec>();
+
+    // Assert
+    assert_eq!(today, DueDateSpec::today());
+    assert_eq!(fixed, DueDateSpec::in_days(3).with_interval());
+    assert!(matches!(reversed, Err(AnkiError::ValidationError(_))));
+    assert!(matches!(garbage, Err(AnkiError::ValidationError(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_cards() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "answerCards",
+            "version": 6,
+            "params": {
+                "answers": [
+                    {"cardId": 1498938915662_u64, "ease": 2},
+                    {"cardId": 1502098034048_u64, "ease": 4}
+                ]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [true, false],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let results = client.cards().answer_cards(&[
+        (CardId(1498938915662), Ease::Hard),
+        (CardId(1502098034048), Ease::Easy),
+    ]);
+
+    // Assert
+    mock.assert();
+    assert_eq!(results?, vec![true, false]);
+
+    Ok(())
+}
+
+#[test]
+fn test_are_cards_suspended() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "areSuspended",
+            "version": 6,
+            "params": {
+                "cards": [1483959291685_u64, 1483959293217_u64, 1234567891234_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [false, true, null],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let results = client.cards().are_suspended(&[
+        CardId(1483959291685),
+        CardId(1483959293217),
+        CardId(1234567891234),
+    ]);
+
+    // Assert
+    mock.assert();
+
+    let results = results?;
+    assert_eq!(results.len(), 2);
+    assert!(!results[&CardId(1483959291685)]);
+    assert!(results[&CardId(1483959293217)]);
+    assert!(!results.contains_key(&CardId(1234567891234)));
+
+    Ok(())
+}
+
+#[test]
+fn test_are_cards_suspended_rejects_missing_results() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "areSuspended"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [false],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let results = client
+        .cards()
+        .are_suspended(&[CardId(1483959291685), CardId(1483959293217)]);
+
+    // Assert
+    assert!(matches!(results, Err(AnkiError::JsonError(_))));
+}
+
+#[test]
+fn test_get_intervals() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "getIntervals",
+            "version": 6,
+            "params": {
+                "cards": [1502298033753_u64, 1502298036657_u64, 1502298036658_u64],
+                "complete": true
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [
+                    [-120, -180, -240, -300, -360, -14400],
+                    [-120, -180, -240, -300, -360, -14400, 1, 3],
+                    0
+                ],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let intervals = client.cards().get_interval_history(&[
+        CardId(1502298033753),
+        CardId(1502298036657),
+        CardId(1502298036658),
+    ]);
+
+    // Assert
+    mock.assert();
+
+    let intervals = intervals?;
+    assert_eq!(intervals.len(), 3);
+    let learning: Vec<Interval> = [120, 180, 240, 300, 360, 14400]
+        .into_iter()
+        .map(Interval::Seconds)
+        .collect();
+    assert_eq!(intervals[&CardId(1502298033753)], learning);
+    assert_eq!(
+        intervals[&CardId(1502298036657)][5..],
+        [
+            Interval::Seconds(14400),
+            Interval::Days(1),
+            Interval::Days(3)
+        ]
+    );
+    assert!(intervals[&CardId(1502298036658)].is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_cards_info() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "cardsInfo",
+            "version": 6,
+            "params": {
+                "cards": [1498938915662_u64, 1502098034048_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [
+                    {
+                        "answer": "back content",
+                        "question": "front content",
+                        "deckName": "Default",
+                        "modelName": "Basic",
+                        "fieldOrder": 1,
+                        "fields": {
+                            "Front": {"value": "front content", "order": 0},
+                            "Back": {"value": "back content", "order": 1}
+                        },
+                        "css": "p {font-family:Arial;}",
+                        "cardId": 1498938915662_u64,
+                        "interval": 16,
+                        "note": 1502298033753_u64,
+                        "ord": 1,
+                        "type": 0,
+                        "queue": 0,
+                        "due": 1,
+                        "reps": 1,
+                        "lapses": 0,
+                        "left": 6,
+                        "mod": 1629454092
+                    },
+                    {
+                        "answer": "back content",
+                        "question": "front content",
+                        "deckName": "Default",
+                        "modelName": "Basic",
+                        "fieldOrder": 0,
+                        "fields": {
+                            "Front": {"value": "front content", "order": 0},
+                            "Back": {"value": "back content", "order": 1}
+                        },
+                        "css": "p {font-family:Arial;}",
+                        "cardId": 1502098034048_u64,
+                        "interval": 23,
+                        "note": 1502298033753_u64,
+                        "ord": 1,
+                        "type": 0,
+                        "queue": 0,
+                        "due": 1,
+                        "reps": 1,
+                        "lapses": 0,
+                        "left": 6
+                    }
+                ],
+                "error": null
+            }));
+    });
+
+    let notes_mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "notesInfo",
+            "version": 6,
+            "params": {
+                "notes": [1502298033753_u64]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [{
+                    "noteId": 1502298033753_u64,
+                    "modelName": "Basic",
+                    "tags": ["verbs"],
+                    "fields": {
+                        "Front": {"value": "front content", "order": 0},
+                        "Back": {"value": "back content", "order": 1}
+                    }
+                }],
+                "error": null
+            }));
+    });
+
+    let models_mock = server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "findModelsByName",
+            "version": 6,
+            "params": {
+                "modelNames": ["Basic"]
+            }
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [{
+                    "id": 1483883011648_u64,
+                    "name": "Basic",
+                    "flds": [
+                        {"name": "Front", "ord": 0},
+                        {"name": "Back", "ord": 1}
+                    ]
+                }],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let cards_info = client
+        .cards()
+        .get_info(&[CardId(1498938915662), CardId(1502098034048)]);
+
+    // Assert
+    mock.assert();
+    notes_mock.assert();
+    models_mock.assert();
+
+    let cards_info = cards_info?;
+    assert_eq!(cards_info.len(), 2);
+    assert_eq!(cards_info[0].id().value(), 1498938915662);
+    assert_eq!(cards_info[0].note_id().value(), 1502298033753);
+    assert_eq!(cards_info[0].deck_name(), "Default");
+    assert_eq!(cards_info[0].model().name(), "Basic");
+    assert_eq!(cards_info[0].question(), "front content");
+    assert_eq!(cards_info[0].answer(), "back content");
+    assert_eq!(cards_info[0].front_value().unwrap(), "front content");
+    assert!(cards_info[0].has_tag("verbs"));
+    assert_eq!(cards_info[0].interval(), Interval::Days(16));
+    assert_eq!(cards_info[0].queue(), CardQueue::New);
+    assert_eq!(cards_info[0].modified(), 1629454092);
+
+    assert_eq!(cards_info[1].id().value(), 1502098034048);
+    assert_eq!(cards_info[1].deck_name(), "Default");
+    assert_eq!(cards_info[1].model().name(), "Basic");
+    assert_eq!(cards_info[1].question(), "front content");
+    assert_eq!(cards_info[1].answer(), "back content");
+    assert_eq!(cards_info[1].interval(), Interval::Days(23));
+    assert_eq!(cards_info[1].card_type(), CardType::New);
+
+    Ok(())
+}
+
+#[test]
+fn test_cards_info_reports_malformed_cards() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "cardsInfo"}"#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": [
+                    {},
+                    {
+                        "cardId": 1498938915662_u64,
+                        "note": 1502298033753_u64,
+                        "deckName": "Default",
+                        "modelName": "Basic",
+                        "ord": 0,
+                        "fields": {
+                            "Front": {"value": "front content", "order": null}
+                        },
+                        "type": 0,
+                        "queue": 0,
+                        "due": 1,
+                        "interval": 0
+                    }
+                ],
+                "error": null
+            }));
+    });
+
+    let client = create_mock_client(&server);
+
+    // Act
+    let cards_info = client.cards().get_info(&[CardId(1), CardId(1498938915662)]);
+
+    // Assert
+    mock.assert();
+    assert!(matches!(cards_info, Err(AnkiError::JsonError(_))));
+}
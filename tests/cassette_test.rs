@@ -0,0 +1,67 @@
+#[cfg(feature = "fake")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ankiconnect_rs::cassette::{Interaction, ReplaySender};
+#[cfg(feature = "fake")]
+use ankiconnect_rs::{cassette::RecordingSender, fake::FakeAnki, Result};
+use ankiconnect_rs::{AnkiClient, AnkiConnectResponse, AnkiError};
+use serde_json::json;
+
+// Helper function to get a cassette path that is unique to the test
+#[cfg(feature = "fake")]
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cassette-{}-{}.jsonl", name, std::process::id()))
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_record_and_replay_session() -> Result<()> {
+    // Arrange
+    let path = cassette_path("session");
+    let recorder = RecordingSender::create(&path, Arc::new(FakeAnki::new()))?;
+    let client = AnkiClient::with_sender(Arc::new(recorder));
+    let deck_id = client.decks().create("Recorded")?;
+    let recorded_decks = client.decks().get_all()?;
+    let missing = client.decks().get_stat("Missing");
+
+    // Act
+    let replay = Arc::new(ReplaySender::from_file(&path)?);
+    let client = AnkiClient::with_sender(replay.clone());
+    let replayed_id = client.decks().create("Recorded")?;
+    let replayed_decks = client.decks().get_all()?;
+    let replayed_missing = client.decks().get_stat("Missing");
+
+    // Assert
+    assert_eq!(replayed_id, deck_id);
+    assert_eq!(replayed_decks.len(), recorded_decks.len());
+    assert_eq!(missing.is_err(), replayed_missing.is_err());
+    assert_eq!(replay.remaining(), 0);
+
+    std::fs::remove_file(path).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_replay_fails_on_unexpected_request() {
+    // Arrange
+    let replay = Arc::new(ReplaySender::new(vec![Interaction {
+        action: "createDeck".to_string(),
+        params: Some(json!({"deck": "Expected"})),
+        response: AnkiConnectResponse::success(json!(1519323742721_u64)),
+    }]));
+    let client = AnkiClient::with_sender(replay.clone());
+
+    // Act
+    let wrong_params = client.decks().create("Other");
+    let wrong_action = client.version();
+    let expected = client.decks().create("Expected");
+    let exhausted = client.decks().create("Expected");
+
+    // Assert
+    assert!(matches!(wrong_params, Err(AnkiError::CassetteError(_))));
+    assert!(matches!(wrong_action, Err(AnkiError::CassetteError(_))));
+    assert_eq!(expected.unwrap().0, 1519323742721);
+    assert!(matches!(exhausted, Err(AnkiError::CassetteError(_))));
+    assert_eq!(replay.remaining(), 0);
+}
@@ -519,7 +519,10 @@ fn test_deck_not_found_error() -> Result<()> {
     let err = result.unwrap_err();
     assert!(matches!(
         err,
-        AnkiError::AnkiConnectError(AnkiConnectError::DeckNotFound(_))
+        AnkiError::ActionFailed {
+            error: AnkiConnectError::DeckNotFound(_),
+            ..
+        }
     ));
     Ok(())
 }
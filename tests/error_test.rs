@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use ankiconnect_rs::{
+    AnkiClient, AnkiConnectError, AnkiConnectResponse, AnkiError, RequestSender, Result,
+};
+use serde_json::{json, Value};
+
+/// Test double that answers every request with the same AnkiConnect error
+struct FailingSender {
+    message: &'static str,
+}
+
+impl RequestSender for FailingSender {
+    fn send_raw(&self, _action: &str, _params: Option<Value>) -> Result<AnkiConnectResponse> {
+        Ok(AnkiConnectResponse::error(self.message))
+    }
+}
+
+// Helper function to create a client whose requests all fail with the given message
+fn create_failing_client(message: &'static str) -> AnkiClient {
+    AnkiClient::with_sender(Arc::new(FailingSender { message }))
+}
+
+#[test]
+fn test_error_carries_action_params_and_message() {
+    // Arrange
+    let client = create_failing_client("collection is not available");
+
+    // Act
+    let result = client
+        .media()
+        .store_from_base64("aGVsbG8gd29ybGQ=", "hello.txt", false);
+
+    // Assert
+    let error = result.unwrap_err();
+    match &error {
+        AnkiError::ActionFailed {
+            action,
+            params,
+            message,
+            error,
+        } => {
+            assert_eq!(action, "storeMediaFile");
+            assert_eq!(message, "collection is not available");
+            assert!(matches!(error, AnkiConnectError::CollectionUnavailable));
+
+            let params = params.as_deref().unwrap();
+            assert!(params.contains("hello.txt"));
+            assert!(!params.contains("aGVsbG8gd29ybGQ="));
+        }
+        other => panic!("Expected ActionFailed, got {:?}", other),
+    }
+    assert_eq!(error.action(), Some("storeMediaFile"));
+    assert!(error.is_retryable());
+    assert!(error.to_string().contains("storeMediaFile"));
+}
+
+#[test]
+fn test_recognized_errors() {
+    let cases = [
+        ("note was not found: 1502298033753", "NoteNotFound"),
+        ("Card was not found: 1498938915662", "CardNotFound"),
+        ("field was not found in Basic: Extra", "FieldNotFound"),
+        ("template was not found in Basic: Card 3", "TemplateError"),
+        ("Template name already exists: Card 2", "TemplateError"),
+        ("cannot convert the templates of a cloze note type", "Other"),
+        // The Kelvin sign lowercases to a shorter ASCII `k`
+        ("DEC\u{212A} WAS NOT FOUND: Spanish", "Other"),
+        (
+            "cannot create note because it is a duplicate in deck",
+            "DuplicateNote",
+        ),
+        ("collection is not available", "CollectionUnavailable"),
+        (
+            "[Errno 2] No such file or directory: '/tmp/missing.png'",
+            "MediaError",
+        ),
+        ("something unexpected", "Other"),
+    ];
+
+    for (message, expected) in cases {
+        // Arrange
+        let client = create_failing_client(message);
+
+        // Act
+        let error = client.raw("someAction", json!({})).unwrap_err();
+
+        // Assert
+        let kind = format!("{:?}", error.anki_connect_error().unwrap());
+        assert!(
+            kind.starts_with(expected),
+            "'{}' was parsed as {}",
+            message,
+            kind
+        );
+    }
+}
+
+#[test]
+fn test_error_helpers() {
+    // Arrange
+    let not_found = create_failing_client("deck was not found: Missing");
+    let duplicate = create_failing_client("cannot create note because it is a duplicate");
+
+    // Act
+    let not_found = not_found.decks().get_stat("Missing").unwrap_err();
+    let duplicate = duplicate.raw("addNote", json!({})).unwrap_err();
+
+    // Assert
+    assert!(not_found.is_not_found());
+    assert!(!not_found.is_duplicate());
+    assert!(!not_found.is_retryable());
+    assert!(duplicate.is_duplicate());
+    assert!(!duplicate.is_not_found());
+}
@@ -0,0 +1,382 @@
+#![cfg(feature = "fake")]
+
+use std::sync::Arc;
+use std::thread;
+
+use ankiconnect_rs::fake::FakeAnki;
+use ankiconnect_rs::{
+    AnkiClient, AnkiConnectError, AnkiError, CardId, CardQueue, CardType, DueDateSpec, Ease,
+    EaseFactor, ForgetOptions, Interval, NoteBuilder, QueryBuilder, Result,
+};
+use serde_json::json;
+
+// Helper function to create a client backed by a fresh fake
+fn create_fake_client() -> (Arc<FakeAnki>, AnkiClient) {
+    let fake = Arc::new(FakeAnki::new());
+    let client = AnkiClient::with_sender(fake.clone());
+    (fake, client)
+}
+
+// Helper function to add a note to the "Default" deck and get the IDs of its cards
+fn add_card(client: &AnkiClient, model: &str, front: &str, back: &str) -> Result<Vec<CardId>> {
+    let deck = client.decks().get_by_name("Default")?.unwrap();
+    let model = client.models().get_by_name(model)?.unwrap();
+    let note = NoteBuilder::new(model.clone())
+        .with_field(model.field_ref("Front").unwrap(), front)
+        .with_field(model.field_ref("Back").unwrap(), back)
+        .build()
+        .unwrap();
+    client.cards().add_note(&deck, note, false, None)?;
+    client
+        .cards()
+        .find(&QueryBuilder::new().field("Front").is(front).build())
+}
+
+#[test]
+fn test_add_and_find_notes() -> Result<()> {
+    // Arrange
+    let (fake, client) = create_fake_client();
+    client.decks().create("Spanish::Vocab")?;
+    let deck = client.decks().get_by_name("Spanish::Vocab")?.unwrap();
+    let model = client.models().get_by_name("Basic")?.unwrap();
+    let front = model.field_ref("Front").unwrap();
+    let back = model.field_ref("Back").unwrap();
+
+    // Act
+    let note = NoteBuilder::new(model.clone())
+        .with_field(front, "biblioteca")
+        .with_field(back, "the library")
+        .with_tag("spanish-vocab")
+        .build()
+        .unwrap();
+    let note_id = client.cards().add_note(&deck, note, false, None)?;
+
+    let by_deck = client
+        .cards()
+        .find(&QueryBuilder::deck("Spanish").build())?;
+    let by_field = client
+        .cards()
+        .find_notes(&QueryBuilder::new().field("Front").is("biblioteca").build())?;
+    let by_tag = client
+        .cards()
+        .find_notes(&QueryBuilder::new().not().has_tag("spanish-vocab").build())?;
+
+    // Assert
+    assert_eq!(by_deck.len(), 1);
+    assert_eq!(by_field, vec![note_id]);
+    assert!(by_tag.is_empty());
+
+    let collection = fake.collection();
+    let stored = &collection.notes[&note_id.value()];
+    assert_eq!(stored.fields["Back"], "the library");
+    assert_eq!(collection.cards[&by_deck[0].0].note_id, note_id.value());
+
+    Ok(())
+}
+
+#[test]
+fn test_add_duplicate_note_fails() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let deck = client.decks().get_by_name("Default")?.unwrap();
+    let model = client.models().get_by_name("Basic")?.unwrap();
+    let build_note = || {
+        NoteBuilder::new(model.clone())
+            .with_field(model.field_ref("Front").unwrap(), "perro")
+            .with_field(model.field_ref("Back").unwrap(), "dog")
+            .build()
+            .unwrap()
+    };
+    client.cards().add_note(&deck, build_note(), false, None)?;
+
+    // Act
+    let result = client.cards().add_note(&deck, build_note(), false, None);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::DuplicateNote,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_deck_is_reported() {
+    // Arrange
+    let (_, client) = create_fake_client();
+
+    // Act
+    let result = client.decks().get_stat("Missing");
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_batch_runs_against_fake() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let mut batch = client.batch();
+    let created = batch.add::<_, u64>("createDeck", Some(json!({"deck": "Batch"})))?;
+    let names = batch.add::<(), Vec<String>>("deckNames", None)?;
+    let stats = batch.add::<_, serde_json::Value>(
+        "deleteDecks",
+        Some(json!({"decks": ["Batch"], "cardsToo": false})),
+    )?;
+
+    // Act
+    let results = batch.send()?;
+
+    // Assert
+    let deck_id = results.get(&created)?;
+    assert!(deck_id > 0);
+    assert_eq!(results.get(&names)?, vec!["Batch", "Default"]);
+    assert!(matches!(
+        results.get(&stats),
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::Other(_),
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_media_round_trip() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+
+    // Act
+    let filename = client
+        .media()
+        .store_from_base64("aGVsbG8=", "hello.txt", false)?;
+    let data = client.media().retrieve_file(&filename)?;
+
+    // Assert
+    assert_eq!(filename, "hello.txt");
+    assert_eq!(data, "aGVsbG8=");
+
+    Ok(())
+}
+
+#[test]
+fn test_clones_share_the_connection_across_threads() -> Result<()> {
+    fn assert_send_sync<T: Send + Sync + Clone>() {}
+    assert_send_sync::<AnkiClient>();
+
+    // Arrange
+    let (fake, client) = create_fake_client();
+
+    // Act
+    let workers: Vec<_> = ["Workers::A", "Workers::B"]
+        .into_iter()
+        .map(|name| {
+            let decks = client.decks_owned();
+            thread::spawn(move || decks.create(name))
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap()?;
+    }
+    let names = client.clone().decks().get_all()?;
+
+    // Assert
+    assert!(fake.collection().decks.contains_key("Workers::A"));
+    assert!(names.iter().any(|deck| deck.name() == "Workers::B"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_card_info_skips_missing_cards() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_id = add_card(&client, "Basic", "gato", "cat")?[0];
+    let note_id = client
+        .cards()
+        .find_notes(&QueryBuilder::deck("Default").build())?[0];
+
+    // More IDs than fit into a single request
+    let mut card_ids: Vec<CardId> = (1..=600).map(CardId).collect();
+    card_ids.push(card_id);
+
+    // Act
+    let cards = client.cards().get_info(&card_ids)?;
+
+    // Assert
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].id(), card_id);
+    assert_eq!(cards[0].note_id(), note_id);
+    assert_eq!(cards[0].deck_name(), "Default");
+    assert_eq!(cards[0].template_ord(), 0);
+    assert_eq!(cards[0].question(), "gato");
+    assert_eq!(cards[0].field_value("Back").unwrap(), "cat");
+    assert_eq!(cards[0].queue(), CardQueue::New);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_ease_factors_skips_missing_cards() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_ids = add_card(&client, "Basic (and reversed card)", "perro", "dog")?;
+    client
+        .cards()
+        .set_ease_factors(&[(card_ids[0], EaseFactor::new(2300)?)])?;
+
+    // Act
+    let factors = client
+        .cards()
+        .get_ease_factors(&[card_ids[0], card_ids[1], CardId(1)])?;
+
+    // Assert
+    assert_eq!(factors.len(), 2);
+    assert_eq!(factors[&card_ids[0]], Some(EaseFactor::new(2300)?));
+    assert_eq!(factors[&card_ids[1]], None);
+
+    Ok(())
+}
+
+#[test]
+fn test_card_state_queries() -> Result<()> {
+    // Arrange
+    let (fake, client) = create_fake_client();
+    let card_ids = add_card(&client, "Basic (and reversed card)", "casa", "house")?;
+
+    // Turn the first card into a review card that is due today
+    let mut collection = fake.collection();
+    let review = collection.cards.get_mut(&card_ids[0].value()).unwrap();
+    review.card_type = 2;
+    review.queue = 2;
+    review.due = collection.today;
+    review.interval = 12;
+    review.reps = 3;
+    fake.replace_collection(collection);
+    client.cards().suspend_cards(&card_ids[1..])?;
+
+    // Act
+    let suspended = client.cards().are_suspended(&card_ids)?;
+    let due = client.cards().are_due(&card_ids)?;
+    let intervals = client.cards().get_intervals(&card_ids)?;
+    let history = client.cards().get_interval_history(&card_ids)?;
+
+    // Assert
+    assert!(!suspended[&card_ids[0]]);
+    assert!(suspended[&card_ids[1]]);
+    assert!(due[&card_ids[0]]);
+    assert!(!due[&card_ids[1]]);
+    assert_eq!(intervals[&card_ids[0]], Interval::Days(12));
+    assert_eq!(history[&card_ids[0]], vec![Interval::Days(12)]);
+    assert!(history[&card_ids[1]].is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_reschedule_cards() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_ids = add_card(&client, "Basic", "libro", "book")?;
+
+    // Act
+    client
+        .cards()
+        .set_due_date(&card_ids, DueDateSpec::in_days(4).with_interval())?;
+    let scheduled = client.cards().get_info(&card_ids)?;
+    client.cards().relearn_cards(&card_ids)?;
+    let relearning = client.cards().get_info(&card_ids)?;
+    client
+        .cards()
+        .forget_cards(&card_ids, ForgetOptions::default())?;
+    let forgotten = client.cards().get_info(&card_ids)?;
+
+    // Assert
+    assert_eq!(scheduled[0].queue(), CardQueue::Review);
+    assert_eq!(scheduled[0].interval(), Interval::Days(4));
+    assert_eq!(relearning[0].card_type(), CardType::Relearning);
+    assert_eq!(forgotten[0].card_type(), CardType::New);
+    assert_eq!(forgotten[0].interval(), Interval::Days(0));
+
+    Ok(())
+}
+
+#[test]
+fn test_forget_options() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_ids = add_card(&client, "Basic (and reversed card)", "perro", "dog")?;
+    client
+        .cards()
+        .answer_cards(&[(card_ids[0], Ease::Good), (card_ids[1], Ease::Good)])?;
+
+    // Act
+    client
+        .cards()
+        .forget_cards(&card_ids[..1], ForgetOptions::default())?;
+    client.cards().forget_cards(
+        &card_ids[1..],
+        ForgetOptions {
+            restore_position: false,
+            reset_counts: true,
+        },
+    )?;
+    let forgotten = client.cards().get_info(&card_ids)?;
+
+    // Assert
+    assert_eq!(forgotten[0].due(), 0);
+    assert_eq!(forgotten[0].reps(), 1);
+    assert_eq!(forgotten[1].due(), 2);
+    assert_eq!(forgotten[1].reps(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_cards() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_id = add_card(&client, "Basic", "mesa", "table")?[0];
+
+    // Act
+    let results = client
+        .cards()
+        .answer_cards(&[(card_id, Ease::Good), (CardId(1), Ease::Again)])?;
+    let card = client.cards().get_info(&[card_id])?.remove(0);
+
+    // Assert
+    assert_eq!(results, vec![true, false]);
+    assert_eq!(card.queue(), CardQueue::Review);
+    assert_eq!(card.reps(), 1);
+    assert!(card.ease_factor().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_cards_rejects_invalid_ease() -> Result<()> {
+    // Arrange
+    let (_, client) = create_fake_client();
+    let card_id = add_card(&client, "Basic", "mesa", "table")?[0];
+
+    // Act
+    let result = client.raw(
+        "answerCards",
+        json!({"answers": [{"cardId": card_id.0, "ease": 3}, {"cardId": card_id.0, "ease": 5}]}),
+    );
+    let card = client.cards().get_info(&[card_id])?.remove(0);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionFailed { ref message, .. }) if message == "invalid ease: 5"
+    ));
+    assert_eq!(card.reps(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use std::net::TcpListener;
+#[cfg(feature = "fake")]
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "fake")]
+use ankiconnect_rs::{fake::FakeAnki, Result};
+use ankiconnect_rs::{AnkiClient, AnkiError};
+use httpmock::prelude::*;
+use serde_json::json;
+
+// Helper function to get a port nothing listens on
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_wait_until_ready_returns_version() -> Result<()> {
+    // Arrange
+    let client = AnkiClient::with_sender(Arc::new(FakeAnki::new()));
+
+    // Act
+    let version = client.wait_until_ready(Duration::from_secs(1))?;
+
+    // Assert
+    assert_eq!(version, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_wait_until_ready_times_out() {
+    // Arrange
+    let client = AnkiClient::with_connection("127.0.0.1", unused_port());
+    let started = Instant::now();
+
+    // Act
+    let result = client.wait_until_ready(Duration::from_millis(600));
+
+    // Assert
+    assert!(
+        matches!(result, Err(AnkiError::NotReady { waited, .. }) if waited >= Duration::from_millis(600))
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+#[cfg(feature = "fake")]
+fn test_health_reports_version_and_profile() {
+    // Arrange
+    let client = AnkiClient::with_sender(Arc::new(FakeAnki::new()));
+
+    // Act
+    let health = client.health();
+
+    // Assert
+    assert!(health.is_healthy());
+    assert_eq!(health.version, Some(6));
+    assert_eq!(health.profile.as_deref(), Some("User 1"));
+    assert!(health.latency.is_some());
+    assert_eq!(health.error, None);
+}
+
+#[test]
+fn test_health_without_profile_support() {
+    // Arrange
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"action": "version"}"#);
+        then.status(200)
+            .json_body(json!({"result": 6, "error": null}));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"action": "getActiveProfile"}"#);
+        then.status(200)
+            .json_body(json!({"result": null, "error": "unsupported action"}));
+    });
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    // Act
+    let health = client.health();
+
+    // Assert
+    assert!(health.is_healthy());
+    assert_eq!(health.version, Some(6));
+    assert_eq!(health.profile, None);
+}
+
+#[test]
+fn test_health_when_unreachable() {
+    // Arrange
+    let client = AnkiClient::with_connection("127.0.0.1", unused_port());
+
+    // Act
+    let health = client.health();
+
+    // Assert
+    assert!(!health.reachable);
+    assert!(!health.is_healthy());
+    assert_eq!(health.version, None);
+    assert!(health.error.is_some());
+}
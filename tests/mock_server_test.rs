@@ -0,0 +1,190 @@
+#![cfg(feature = "fake")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+use ankiconnect_rs::fake::Collection;
+use ankiconnect_rs::{AnkiClient, AnkiConnectError, AnkiError, Result};
+use serde_json::{json, Value};
+
+/// A running mock-ankiconnect process that is killed when dropped
+struct MockAnkiConnect {
+    child: Child,
+    port: u16,
+}
+
+impl MockAnkiConnect {
+    fn start(args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mock-ankiconnect"))
+            .args(["--port", "0"])
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start mock-ankiconnect");
+
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let port = line.trim().rsplit(':').next().unwrap().parse().unwrap();
+
+        Self { child, port }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, path)
+    }
+}
+
+impl Drop for MockAnkiConnect {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_client_talks_to_mock_server() -> Result<()> {
+    // Arrange
+    let server = MockAnkiConnect::start(&[]);
+    let client = AnkiClient::with_connection("127.0.0.1", server.port);
+
+    // Act
+    let deck_id = client.decks().create("Mock::Deck")?;
+    let decks = client.decks().get_all()?;
+
+    // Assert
+    assert!(decks
+        .iter()
+        .any(|deck| deck.name() == "Mock::Deck" && deck.id() == deck_id));
+
+    Ok(())
+}
+
+#[test]
+fn test_idle_connection_does_not_block_requests() -> Result<()> {
+    // Arrange
+    let server = MockAnkiConnect::start(&[]);
+    let client = AnkiClient::with_connection("127.0.0.1", server.port);
+    let _idle = TcpStream::connect(("127.0.0.1", server.port)).unwrap();
+
+    // Act
+    let version = client.version()?;
+
+    // Assert
+    assert_eq!(version, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_and_dump_fixture() -> Result<()> {
+    // Arrange
+    let mut fixture = Collection::empty();
+    fixture.decks.insert("Fixture".to_string(), 42);
+    let path = std::env::temp_dir().join(format!("mock-ankiconnect-{}.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&fixture).unwrap()).unwrap();
+
+    let server = MockAnkiConnect::start(&["--load", path.to_str().unwrap()]);
+    let client = AnkiClient::with_connection("127.0.0.1", server.port);
+
+    // Act
+    client.decks().create("Created")?;
+    let dumped: Collection = ureq::get(&server.url("/collection"))
+        .call()?
+        .body_mut()
+        .read_json()?;
+
+    // Assert
+    assert_eq!(dumped.decks["Fixture"], 42);
+    assert!(dumped.decks.contains_key("Created"));
+    assert!(dumped.models.is_empty());
+
+    std::fs::remove_file(path).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_api_key_is_enforced() -> Result<()> {
+    // Arrange
+    let server = MockAnkiConnect::start(&["--api-key", "secret"]);
+    let without_key = AnkiClient::with_connection("127.0.0.1", server.port);
+    let with_key = AnkiClient::connect("127.0.0.1", server.port, Some("secret"))?;
+
+    // Act
+    let rejected = without_key.version();
+    let accepted = with_key.version()?;
+
+    // Assert
+    assert!(matches!(
+        rejected,
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::InvalidApiKey,
+            ..
+        })
+    ));
+    assert_eq!(accepted, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_api_key_is_checked_per_multi_action() -> Result<()> {
+    // Arrange
+    let server = MockAnkiConnect::start(&["--api-key", "secret"]);
+    let request = json!({
+        "action": "multi",
+        "version": 6,
+        "key": "secret",
+        "params": {
+            "actions": [
+                {"action": "version", "version": 6, "key": "secret"},
+                {"action": "version", "version": 6, "key": "wrong"},
+                {"action": "version", "version": 6}
+            ]
+        }
+    });
+
+    // Act
+    let response: Value = ureq::post(&server.url("/"))
+        .send_json(&request)?
+        .body_mut()
+        .read_json()?;
+
+    // Assert
+    assert_eq!(response["error"], Value::Null);
+    let results = response["result"].as_array().unwrap();
+    assert_eq!(results[0], json!({"result": 6, "error": null}));
+    for rejected in &results[1..] {
+        assert_eq!(rejected["error"], "valid api key must be provided");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_chunked_body_with_trailers() {
+    // Arrange
+    let server = MockAnkiConnect::start(&[]);
+    let body = r#"{"action": "version", "version": 6}"#;
+    let mut stream = TcpStream::connect(("127.0.0.1", server.port)).unwrap();
+
+    // Act
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\n\
+         Transfer-Encoding: chunked\r\n\r\n\
+         {:x}\r\n{}\r\n0\r\n\
+         X-Checksum: 1234\r\n\r\n",
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    // Assert
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with(r#"{"result":6,"error":null}"#));
+}
@@ -23,79 +23,39 @@ fn test_get_all_models() -> Result<()> {
             .json_body(json!({
                 "result": {
                     "Basic": 1483883011648_u64,
-                    "Basic (and reversed card)": 1483883011644_u64,
-                    "Basic (optional reversed card)": 1483883011631_u64,
                     "Cloze": 1483883011630_u64
                 },
                 "error": null
             }));
     });
 
-    // Also mock the field names request for each model
-    let model_fields_mock1 = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
-            "version": 6,
-            "params": {
-                "modelName": "Basic"
-            }
-        }));
-
-        then.status(200)
-            .header("content-type", "application/json")
-            .json_body(json!({
-                "result": ["Front", "Back"],
-                "error": null
-            }));
-    });
-
-    let model_fields_mock2 = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
-            "version": 6,
-            "params": {
-                "modelName": "Basic (and reversed card)"
-            }
-        }));
-
-        then.status(200)
-            .header("content-type", "application/json")
-            .json_body(json!({
-                "result": ["Front", "Back"],
-                "error": null
-            }));
-    });
-
-    let model_fields_mock3 = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
-            "version": 6,
-            "params": {
-                "modelName": "Basic (optional reversed card)"
-            }
-        }));
-
-        then.status(200)
-            .header("content-type", "application/json")
-            .json_body(json!({
-                "result": ["Front", "Back", "Add Reverse"],
-                "error": null
-            }));
-    });
-
-    let model_fields_mock4 = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
-            "version": 6,
-            "params": {
-                "modelName": "Cloze"
-            }
-        }));
+    // All models are fetched with a single request, whatever the order of the IDs
+    let model_details_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "findModelsById"}"#);
 
         then.status(200)
             .header("content-type", "application/json")
             .json_body(json!({
-                "result": ["Text", "Extra"],
+                "result": [
+                    {
+                        "id": 1483883011648_u64,
+                        "name": "Basic",
+                        "flds": [
+                            {"name": "Back", "ord": 1},
+                            {"name": "Front", "ord": 0}
+                        ]
+                    },
+                    {
+                        "id": 1483883011630_u64,
+                        "name": "Cloze",
+                        "flds": [
+                            {"name": "Text", "ord": 0},
+                            {"name": "Extra", "ord": 1}
+                        ]
+                    }
+                ],
                 "error": null
             }));
     });
@@ -107,15 +67,12 @@ fn test_get_all_models() -> Result<()> {
 
     // Assert
     model_names_mock.assert();
-    model_fields_mock1.assert();
-    model_fields_mock2.assert();
-    model_fields_mock3.assert();
-    model_fields_mock4.assert();
+    model_details_mock.assert_hits(1);
 
     let models = models?;
-    assert_eq!(models.len(), 4);
+    assert_eq!(models.len(), 2);
 
-    // Verify the models
+    // Verify the models, with fields ordered by `ord`
     let basic = models.iter().find(|m| m.name() == "Basic").unwrap();
     assert_eq!(basic.id().0, 1483883011648);
     assert_eq!(basic.fields().len(), 2);
@@ -136,57 +93,44 @@ fn test_get_model_by_name() -> Result<()> {
     // Arrange
     let server = MockServer::start();
 
-    // Mock for getting all models
-    let model_names_mock = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelNamesAndIds",
-            "version": 6
-        }));
-
-        then.status(200)
-            .header("content-type", "application/json")
-            .json_body(json!({
-                "result": {
-                    "Basic": 1483883011648_u64,
-                    "Cloze": 1483883011630_u64
-                },
-                "error": null
-            }));
-    });
-
-    // Mock for getting Basic model fields
-    let basic_names_mock = server.mock(|when, then| {
+    let basic_mock = server.mock(|when, then| {
         when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
+            "action": "findModelsByName",
             "version": 6,
             "params": {
-                "modelName": "Basic"
+                "modelNames": ["Basic"]
             }
         }));
 
         then.status(200)
             .header("content-type", "application/json")
             .json_body(json!({
-                "result": ["Front", "Back"],
+                "result": [{
+                    "id": 1483883011648_u64,
+                    "name": "Basic",
+                    "flds": [
+                        {"name": "Front", "ord": 0},
+                        {"name": "Back", "ord": 1}
+                    ]
+                }],
                 "error": null
             }));
     });
 
-    // Mock for getting Cloze model fields
-    let cloze_names_mock = server.mock(|when, then| {
+    let missing_mock = server.mock(|when, then| {
         when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
+            "action": "findModelsByName",
             "version": 6,
             "params": {
-                "modelName": "Cloze"
+                "modelNames": ["NonExistent"]
             }
         }));
 
         then.status(200)
             .header("content-type", "application/json")
             .json_body(json!({
-                "result": ["Text", "Extra"],
-                "error": null
+                "result": null,
+                "error": "model was not found: NonExistent"
             }));
     });
 
@@ -196,9 +140,7 @@ fn test_get_model_by_name() -> Result<()> {
     let model = client.models().get_by_name("Basic")?;
 
     // Assert
-    model_names_mock.assert();
-    basic_names_mock.assert();
-    cloze_names_mock.assert();
+    basic_mock.assert();
     assert!(model.is_some());
     let model = model.unwrap();
     assert_eq!(model.name(), "Basic");
@@ -209,6 +151,7 @@ fn test_get_model_by_name() -> Result<()> {
 
     // Test with non-existent model
     let non_existent = client.models().get_by_name("NonExistent")?;
+    missing_mock.assert();
     assert!(non_existent.is_none());
 
     Ok(())
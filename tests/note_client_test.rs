@@ -32,32 +32,24 @@ fn test_add_note() -> Result<()> {
     // Mock for getting model info
     let model_info_mock = server.mock(|when, then| {
         when.method(POST).path("/").json_body(json!({
-            "action": "modelNamesAndIds",
-            "version": 6
-        }));
-
-        then.status(200)
-            .header("content-type", "application/json")
-            .json_body(json!({
-                "result": {"Basic": 1483883011648_u64},
-                "error": null
-            }));
-    });
-
-    // Mock for getting model fields
-    let model_fields_mock = server.mock(|when, then| {
-        when.method(POST).path("/").json_body(json!({
-            "action": "modelFieldNames",
+            "action": "findModelsByName",
             "version": 6,
             "params": {
-                "modelName": "Basic"
+                "modelNames": ["Basic"]
             }
         }));
 
         then.status(200)
             .header("content-type", "application/json")
             .json_body(json!({
-                "result": ["Front", "Back"],
+                "result": [{
+                    "id": 1483883011648_u64,
+                    "name": "Basic",
+                    "flds": [
+                        {"name": "Front", "ord": 0},
+                        {"name": "Back", "ord": 1}
+                    ]
+                }],
                 "error": null
             }));
     });
@@ -103,7 +95,6 @@ fn test_add_note() -> Result<()> {
     let model = client.models().get_by_name("Basic");
 
     model_info_mock.assert();
-    model_fields_mock.assert();
     let model = model?.unwrap();
 
     // Create note using builder
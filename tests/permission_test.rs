@@ -0,0 +1,97 @@
+use ankiconnect_rs::{AnkiClient, AnkiConnectError, AnkiError, Permission, Result};
+use httpmock::prelude::*;
+use serde_json::{json, Value};
+
+// Helper function to mock the response to requestPermission
+fn mock_permission(server: &MockServer, result: Value) {
+    server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "action": "requestPermission",
+            "version": 6
+        }));
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "result": result,
+                "error": null
+            }));
+    });
+}
+
+#[test]
+fn test_request_permission_granted() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_permission(
+        &server,
+        json!({"permission": "granted", "requireApikey": true, "version": 6}),
+    );
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    // Act
+    let permission = client.request_permission()?;
+
+    // Assert
+    assert_eq!(
+        permission,
+        Permission::Granted {
+            require_api_key: true,
+            version: 6
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_request_permission_denied() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    mock_permission(&server, json!({"permission": "denied"}));
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    // Act
+    let permission = client.request_permission()?;
+
+    // Assert
+    assert_eq!(permission, Permission::Denied);
+    assert!(!permission.is_granted());
+
+    Ok(())
+}
+
+#[test]
+fn test_connect_fails_when_permission_is_denied() {
+    // Arrange
+    let server = MockServer::start();
+    mock_permission(&server, json!({"permission": "denied"}));
+
+    // Act
+    let result = AnkiClient::connect(&server.host(), server.port(), None);
+
+    // Assert
+    assert!(matches!(result, Err(AnkiError::PermissionDenied)));
+}
+
+#[test]
+fn test_connect_fails_when_api_key_is_missing() {
+    // Arrange
+    let server = MockServer::start();
+    mock_permission(
+        &server,
+        json!({"permission": "granted", "requireApikey": true, "version": 6}),
+    );
+
+    // Act
+    let result = AnkiClient::connect(&server.host(), server.port(), None);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionFailed {
+            error: AnkiConnectError::InvalidApiKey,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ankiconnect_rs::limit::{RateLimit, RateLimitedSender};
+use ankiconnect_rs::{AnkiClient, AnkiConnectResponse, AnkiError, RequestSender, Result};
+use serde_json::{json, Value};
+
+/// A sender that records the requests it sees and how many run at once
+#[derive(Default)]
+struct RecordingStub {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    actions: Mutex<Vec<String>>,
+    delay: Duration,
+}
+
+impl RequestSender for RecordingStub {
+    fn send_raw(&self, action: &str, _params: Option<Value>) -> Result<AnkiConnectResponse> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        self.actions.lock().unwrap().push(action.to_string());
+        thread::sleep(self.delay);
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(AnkiConnectResponse::success(json!(6)))
+    }
+}
+
+/// A sender that blocks the first request until the test releases it
+struct GatedStub {
+    gate: Mutex<Option<mpsc::Receiver<()>>>,
+    actions: Mutex<Vec<String>>,
+}
+
+impl RequestSender for GatedStub {
+    fn send_raw(&self, action: &str, _params: Option<Value>) -> Result<AnkiConnectResponse> {
+        self.actions.lock().unwrap().push(action.to_string());
+        if let Some(gate) = self.gate.lock().unwrap().take() {
+            gate.recv().unwrap();
+        }
+        Ok(AnkiConnectResponse::success(Value::Null))
+    }
+}
+
+/// A sender that holds each gated action until the test releases it
+#[derive(Default)]
+struct PerActionGates {
+    gates: Mutex<HashMap<String, mpsc::Receiver<()>>>,
+}
+
+impl PerActionGates {
+    fn gate(&self, action: &str) -> mpsc::Sender<()> {
+        let (release, gate) = mpsc::channel();
+        self.gates.lock().unwrap().insert(action.to_string(), gate);
+        release
+    }
+}
+
+impl RequestSender for PerActionGates {
+    fn send_raw(&self, action: &str, _params: Option<Value>) -> Result<AnkiConnectResponse> {
+        let gate = self.gates.lock().unwrap().remove(action);
+        if let Some(gate) = gate {
+            gate.recv().unwrap();
+        }
+        Ok(AnkiConnectResponse::success(Value::Null))
+    }
+}
+
+#[test]
+fn test_max_in_flight_is_enforced() -> Result<()> {
+    // Arrange
+    let stub = Arc::new(RecordingStub {
+        delay: Duration::from_millis(20),
+        ..Default::default()
+    });
+    let limit = RateLimit {
+        max_in_flight: Some(2),
+        requests_per_second: None,
+    };
+    let client = Arc::new(AnkiClient::with_sender(Arc::new(RateLimitedSender::new(
+        stub.clone(),
+        limit,
+    )?)));
+
+    // Act
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            let client = Arc::clone(&client);
+            thread::spawn(move || client.version())
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap()?;
+    }
+
+    // Assert
+    assert_eq!(stub.actions.lock().unwrap().len(), 8);
+    assert!(stub.max_in_flight.load(Ordering::SeqCst) <= 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_requests_per_second_is_enforced() -> Result<()> {
+    // Arrange
+    let limit = RateLimit {
+        max_in_flight: None,
+        requests_per_second: Some(50.0),
+    };
+    let client = AnkiClient::with_sender(Arc::new(RateLimitedSender::new(
+        Arc::new(RecordingStub::default()),
+        limit,
+    )?));
+
+    // Act
+    let started = Instant::now();
+    for _ in 0..6 {
+        client.version()?;
+    }
+
+    // Assert
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    Ok(())
+}
+
+#[test]
+fn test_gui_actions_go_before_waiting_bulk_requests() -> Result<()> {
+    // Arrange
+    let (release, gate) = mpsc::channel();
+    let stub = Arc::new(GatedStub {
+        gate: Mutex::new(Some(gate)),
+        actions: Mutex::new(Vec::new()),
+    });
+    let limit = RateLimit {
+        max_in_flight: Some(1),
+        requests_per_second: None,
+    };
+    let limiter = Arc::new(RateLimitedSender::new(stub.clone(), limit)?);
+    let spawn = |action: &'static str| {
+        let limiter = Arc::clone(&limiter);
+        let worker = thread::spawn(move || limiter.send_raw(action, None));
+        thread::sleep(Duration::from_millis(50));
+        worker
+    };
+
+    // Act
+    let workers = [spawn("addNotes"), spawn("changeDeck"), spawn("guiBrowse")];
+    release.send(()).unwrap();
+    for worker in workers {
+        worker.join().unwrap()?;
+    }
+
+    // Assert
+    assert_eq!(
+        *stub.actions.lock().unwrap(),
+        vec!["addNotes", "guiBrowse", "changeDeck"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_limits_are_rejected() {
+    // Arrange
+    let stub: Arc<dyn RequestSender> = Arc::new(RecordingStub::default());
+
+    // Act
+    let no_slots = RateLimitedSender::new(
+        Arc::clone(&stub),
+        RateLimit {
+            max_in_flight: Some(0),
+            requests_per_second: None,
+        },
+    );
+    let negative_rate = RateLimitedSender::new(
+        stub,
+        RateLimit {
+            max_in_flight: None,
+            requests_per_second: Some(-1.0),
+        },
+    );
+
+    // Assert
+    assert!(matches!(no_slots, Err(AnkiError::ValidationError(_))));
+    assert!(matches!(negative_rate, Err(AnkiError::ValidationError(_))));
+}
+
+#[test]
+fn test_bulk_request_runs_beside_gui_request() -> Result<()> {
+    // Arrange
+    let stub = Arc::new(PerActionGates::default());
+    let release_first = stub.gate("addNotes");
+    let release_second = stub.gate("deckNames");
+    let release_gui = stub.gate("guiBrowse");
+    let limit = RateLimit {
+        max_in_flight: Some(2),
+        requests_per_second: None,
+    };
+    let limiter = Arc::new(RateLimitedSender::new(stub, limit)?);
+    let spawn = |action: &'static str| {
+        let limiter = Arc::clone(&limiter);
+        let worker = thread::spawn(move || limiter.send_raw(action, None));
+        thread::sleep(Duration::from_millis(50));
+        worker
+    };
+    let first = spawn("addNotes");
+    let second = spawn("deckNames");
+    let (done, bulk_done) = mpsc::channel();
+    let bulk = {
+        let limiter = Arc::clone(&limiter);
+        thread::spawn(move || {
+            let result = limiter.send_raw("changeDeck", None);
+            done.send(()).unwrap();
+            result
+        })
+    };
+    thread::sleep(Duration::from_millis(50));
+    let gui = spawn("guiBrowse");
+
+    // Act
+    release_first.send(()).unwrap();
+    release_second.send(()).unwrap();
+    let finished = bulk_done.recv_timeout(Duration::from_secs(2));
+    release_gui.send(()).unwrap();
+
+    // Assert
+    assert!(
+        finished.is_ok(),
+        "the bulk request waited for the GUI request"
+    );
+    for worker in [first, second, bulk, gui] {
+        worker.join().unwrap()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_gui_request_does_not_wait_for_bulk_rate_delay() -> Result<()> {
+    // Arrange
+    let limit = RateLimit {
+        max_in_flight: Some(1),
+        requests_per_second: Some(1.0),
+    };
+    let limiter = Arc::new(RateLimitedSender::new(
+        Arc::new(RecordingStub::default()),
+        limit,
+    )?);
+    limiter.send_raw("addNotes", None)?;
+    let bulk = {
+        let limiter = Arc::clone(&limiter);
+        thread::spawn(move || limiter.send_raw("changeDeck", None))
+    };
+    thread::sleep(Duration::from_millis(50));
+
+    // Act
+    let started = Instant::now();
+    limiter.send_raw("guiBrowse", None)?;
+    let elapsed = started.elapsed();
+
+    // Assert
+    assert!(
+        elapsed < Duration::from_millis(500),
+        "the GUI request waited {elapsed:?} for the bulk request"
+    );
+    bulk.join().unwrap()?;
+
+    Ok(())
+}
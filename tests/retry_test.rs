@@ -0,0 +1,114 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ankiconnect_rs::http::{HttpRequestSender, RetryPolicy};
+use ankiconnect_rs::{AnkiClient, AnkiError};
+use httpmock::prelude::*;
+
+// Helper function to create a client that retries quickly
+fn create_retrying_client(host: &str, port: u16) -> AnkiClient {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        ..RetryPolicy::new(3)
+    };
+    let sender = HttpRequestSender::new(host, port).with_retry_policy(policy);
+    AnkiClient::with_sender(Arc::new(sender))
+}
+
+#[test]
+fn test_refused_connection_is_retried() {
+    // Arrange
+    let port = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let client = create_retrying_client("127.0.0.1", port);
+
+    // Act
+    let result = client.decks().create("Never Created");
+
+    // Assert
+    match result {
+        Err(AnkiError::RequestFailed { action, attempts }) => {
+            assert_eq!(action, "createDeck");
+            assert_eq!(attempts.len(), 3);
+            assert_eq!(attempts[2].attempt, 3);
+        }
+        other => panic!("Expected RequestFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_server_error_is_retried_for_read_actions_only() {
+    // Arrange
+    let server = MockServer::start();
+    let read_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "deckNamesAndIds"}"#);
+        then.status(503);
+    });
+    let write_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "createDeck"}"#);
+        then.status(503);
+    });
+    let client = create_retrying_client(&server.host(), server.port());
+
+    // Act
+    let read = client.decks().get_all();
+    let write = client.decks().create("Test Deck");
+
+    // Assert
+    assert!(matches!(read, Err(AnkiError::RequestFailed { .. })));
+    assert!(matches!(write, Err(AnkiError::HttpError(_))));
+    read_mock.assert_hits(3);
+    write_mock.assert_hits(1);
+}
+
+#[test]
+fn test_read_timeout() {
+    // Arrange
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/");
+        then.status(200)
+            .delay(Duration::from_millis(500))
+            .body(r#"{"result": 6, "error": null}"#);
+    });
+    let sender = HttpRequestSender::new(&server.host(), server.port())
+        .with_read_timeout(Duration::from_millis(50));
+    let client = AnkiClient::with_sender(Arc::new(sender));
+
+    // Act
+    let result = client.version();
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::HttpError(ureq::Error::Timeout(_)))
+    ));
+}
+
+#[test]
+fn test_backoff_with_invalid_multiplier_does_not_panic() {
+    // Arrange
+    let negative = RetryPolicy {
+        multiplier: -2.0,
+        ..RetryPolicy::new(3)
+    };
+    let not_a_number = RetryPolicy {
+        multiplier: f64::NAN,
+        ..RetryPolicy::new(3)
+    };
+
+    // Act
+    let negative_delay = negative.backoff(2);
+    let nan_delay = not_a_number.backoff(2);
+
+    // Assert
+    assert_eq!(negative_delay, Duration::ZERO);
+    assert!(nan_delay <= not_a_number.max_backoff);
+}
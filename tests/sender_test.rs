@@ -0,0 +1,97 @@
+use std::sync::{Arc, Mutex};
+
+use ankiconnect_rs::{
+    Action, AnkiClient, AnkiConnectError, AnkiConnectResponse, AnkiError, RequestSender, Result,
+};
+use serde_json::{json, Value};
+
+/// Test double that records every request and answers with a canned response
+struct StubSender {
+    response: AnkiConnectResponse,
+    requests: Mutex<Vec<(String, Option<Value>)>>,
+}
+
+impl StubSender {
+    fn new(response: AnkiConnectResponse) -> Arc<Self> {
+        Arc::new(Self {
+            response,
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+impl RequestSender for StubSender {
+    fn send_raw(&self, action: &str, params: Option<Value>) -> Result<AnkiConnectResponse> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((action.to_string(), params));
+        Ok(self.response.clone())
+    }
+}
+
+#[test]
+fn test_client_uses_injected_sender() -> Result<()> {
+    // Arrange
+    let sender = StubSender::new(AnkiConnectResponse::success(json!(1519323742721_u64)));
+    let client = AnkiClient::with_sender(sender.clone());
+
+    // Act
+    let deck_id = client.decks().create("Japanese::Tokyo")?;
+
+    // Assert
+    assert_eq!(deck_id.0, 1519323742721);
+    let requests = sender.requests.lock().unwrap();
+    let (action, params) = requests.last().unwrap();
+    assert_eq!(action, "createDeck");
+    assert_eq!(*params, Some(json!({"deck": "Japanese::Tokyo"})));
+
+    Ok(())
+}
+
+#[test]
+fn test_injected_sender_errors_are_parsed() {
+    // Arrange
+    let sender = StubSender::new(AnkiConnectResponse::error("model was not found: Basic"));
+    let client = AnkiClient::with_sender(sender);
+
+    // Act
+    let result = client.models().get_fields_for_name("Basic");
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(AnkiError::ActionFailed { error: AnkiConnectError::ModelNotFound(name), .. }) if name == "Basic"
+    ));
+}
+
+/// An action whose result may be `null`
+struct GetCurrentDeckId;
+
+impl Action for GetCurrentDeckId {
+    const NAME: &'static str = "getCurrentDeckId";
+    type Params = ();
+    type Response = Option<u64>;
+}
+
+#[test]
+fn test_null_results_decode_into_unit_and_option() -> Result<()> {
+    // Arrange
+    let empty = AnkiConnectResponse {
+        result: None,
+        error: None,
+    };
+    let client = AnkiClient::with_sender(StubSender::new(empty));
+
+    // Act
+    let deleted = client.media().delete_file("missing.png");
+    let deck_id = client.execute::<GetCurrentDeckId>(())?;
+    let version = client.version();
+
+    // Assert
+    assert!(deleted.is_ok());
+    assert_eq!(deck_id, None);
+    assert!(matches!(version, Err(AnkiError::JsonError(_))));
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "tracing")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use ankiconnect_rs::{AnkiClient, Result};
+use httpmock::prelude::*;
+use serde_json::json;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Collects the formatted log output in memory
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn test_request_span_redacts_media() -> Result<()> {
+    // Arrange
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"action": "storeMediaFile"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": "hello.txt", "error": null}));
+    });
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(logs.clone())
+        .finish();
+
+    // Act
+    tracing::subscriber::with_default(subscriber, || {
+        client
+            .media()
+            .store_from_base64("aGVsbG8gd29ybGQ=", "hello.txt", false)
+    })?;
+
+    // Assert
+    let output = logs.contents();
+    assert!(output.contains("action=\"storeMediaFile\""), "{}", output);
+    assert!(output.contains("hello.txt"));
+    assert!(output.contains("response_bytes="));
+    assert!(output.contains("duration_ms="));
+    assert!(!output.contains("aGVsbG8gd29ybGQ="));
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_request_is_classified() {
+    // Arrange
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({"result": null, "error": "deck was not found: Missing"}));
+    });
+    let client = AnkiClient::with_connection(&server.host(), server.port());
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(logs.clone())
+        .finish();
+
+    // Act
+    let result =
+        tracing::subscriber::with_default(subscriber, || client.decks().get_stat("Missing"));
+
+    // Assert
+    assert!(result.is_err());
+    let output = logs.contents();
+    assert!(output.contains("action=\"getDeckStats\""), "{}", output);
+    assert!(output.contains("error=\"anki_connect\""), "{}", output);
+}